@@ -1,6 +1,14 @@
-use proc_macro::{self, TokenStream};
+use proc_macro::{self, TokenStream, TokenTree};
 use quote::quote;
 
+// the parser shared with the main crate imports `alloc::format`, as that crate can be no_std
+extern crate alloc;
+
+// `dec!` only parses, the main crate uses the rest
+#[allow(dead_code)]
+#[path = "../../src/parts.rs"]
+mod parts;
+
 /// The smallest exponent that can appear in an f64, though not all mantissas are valid here.
 const NUMBER_EXP_MIN: i32 = -324;
 const NUMBER_EXP_MIN_P: i32 = 324;
@@ -36,3 +44,123 @@ pub fn insert_consts_and_cache_for_powers_of_ten(_: TokenStream) -> TokenStream
 
     output.into()
 }
+
+/// The largest exponent a Decimal can have; beyond it values are infinite.
+const EXP_LIMIT: f64 = 1.79e308;
+
+// the same values as CACHED_POWERS
+fn power_of_10(power: i32) -> f64 {
+    10.0_f64.powi(power)
+}
+
+/// The largest n for which n! fits into an f64.
+const MAX_FACTORIAL: usize = 170;
+
+#[proc_macro]
+pub fn insert_factorials(_: TokenStream) -> TokenStream {
+    // n! is calculated exactly with base 1e9 limbs (least significant first), so that parsing
+    // its decimal digits gives the correctly rounded f64 instead of accumulated rounding errors
+    let mut limbs: Vec<u64> = vec![1];
+    let mut values = Vec::with_capacity(MAX_FACTORIAL + 1);
+    for n in 0..=MAX_FACTORIAL as u64 {
+        if n > 1 {
+            let mut carry = 0;
+            for limb in limbs.iter_mut() {
+                let product = *limb * n + carry;
+                *limb = product % 1_000_000_000;
+                carry = product / 1_000_000_000;
+            }
+            if carry > 0 {
+                limbs.push(carry);
+            }
+        }
+
+        let mut digits = limbs.last().unwrap().to_string();
+        for limb in limbs.iter().rev().skip(1) {
+            digits.push_str(&format!("{limb:09}"));
+        }
+        values.push(digits.parse::<f64>().unwrap());
+    }
+
+    let output = quote! {
+        /// The largest n for which n! fits into an f64.
+        pub(crate) const MAX_FACTORIAL: usize = #MAX_FACTORIAL;
+
+        // macro generated list of n! for n from 0 to MAX_FACTORIAL, each correctly rounded
+        pub(crate) static FACTORIALS: [f64; #MAX_FACTORIAL + 1] = [#(#values),*];
+    };
+
+    output.into()
+}
+
+/// A Decimal from a literal, parsed and normalized at compile time; see `break_infinity_extended::dec`,
+/// which calls this as `dec!($crate; literal)` so the expansion can name the crate wherever it is.
+#[proc_macro]
+pub fn dec(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+    let krate: TokenStream = tokens
+        .by_ref()
+        .take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == ';'))
+        .collect();
+    let literal = tokens.collect();
+
+    let parsed = literal_text(literal)
+        .and_then(|text| parts::parse(&text).map_err(|error| error.to_string()));
+    let (mantissa, exponent) = match parsed {
+        Ok(parts) => parts,
+        Err(message) => {
+            let message = format!("dec!: {message}");
+            return quote!(compile_error!(#message)).into();
+        }
+    };
+
+    let call = if f64::is_nan(mantissa) {
+        quote!(::from_mantissa_exponent_no_normalize(f64::NAN, f64::NAN))
+    } else {
+        let mantissa = float(mantissa);
+        let exponent = float(exponent);
+        quote!(::from_mantissa_exponent_no_normalize(#mantissa, #exponent))
+    };
+    let mut output = krate;
+    output.extend(TokenStream::from(call));
+    output
+}
+
+// the text of a string literal, or of a number literal with an optional sign
+fn literal_text(input: TokenStream) -> Result<String, String> {
+    let mut sign = String::new();
+    let mut tokens = input.into_iter().peekable();
+    if let Some(TokenTree::Punct(punct)) = tokens.peek() {
+        if punct.as_char() == '-' {
+            sign.push('-');
+            tokens.next();
+        }
+    }
+
+    let literal = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) => literal.to_string(),
+        _ => return Err("expected a single number or string literal".to_string()),
+    };
+    match literal
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    {
+        Some(_) if !sign.is_empty() => Err("a string literal can't be negated".to_string()),
+        Some(string) if string.contains('\\') => {
+            Err("escapes aren't supported in string literals".to_string())
+        }
+        Some(string) => Ok(string.to_string()),
+        // digit separators are fine in number literals, type suffixes are not
+        None => Ok(sign + &literal.replace('_', "")),
+    }
+}
+
+// a float literal, with the sign as a separate token
+fn float(value: f64) -> impl quote::ToTokens {
+    let magnitude = value.abs();
+    if value.is_sign_negative() {
+        quote!(-#magnitude)
+    } else {
+        quote!(#magnitude)
+    }
+}
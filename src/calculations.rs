@@ -1,8 +1,15 @@
-use crate::{consts::*, ops::*, Decimal};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::F64Ext;
+use crate::{consts::*, utils::exp_decimal, Decimal};
+use core::borrow::Borrow;
+
+/// Steps after which `search_threshold` gives up and returns the bounds it has found.
+const MAX_SEARCH_STEPS: u64 = 10_000;
 
 /// If you're willing to spend 'resourcesAvailable' and want to buy something
 /// with exponentially increasing cost each purchase (start at priceStart,
 /// multiply by priceRatio, already own currentOwned), how much of it can you buy?
+/// If the purchases are free, there is no limit, which gives `INFINITY`.
 ///
 /// Adapted from Trimps source code.
 pub fn afford_geometric_series(
@@ -11,12 +18,22 @@ pub fn afford_geometric_series(
     price_ratio: &Decimal,
     current_owned: &Decimal,
 ) -> Decimal {
+    if price_start.is_zero() {
+        return INFINITY;
+    }
     let actual_start = price_start * price_ratio.pow(current_owned);
-    Decimal::new(
-        (resources_available / actual_start * (price_ratio - ONE) + ONE).log10()
-            / price_ratio.log10(),
-    )
-    .floor()
+    let count = if *price_ratio == ONE {
+        (resources_available / actual_start).floor()
+    } else {
+        Decimal::new(
+            (resources_available / actual_start * (price_ratio - ONE) + ONE).log10()
+                / price_ratio.log10(),
+        )
+        .floor()
+    };
+    settle_count(count, resources_available, |count| {
+        sum_geometric_series(count, price_start, price_ratio, current_owned)
+    })
 }
 
 /// How much resource would it cost to buy (numItems) items if you already have currentOwned,
@@ -27,13 +44,224 @@ pub fn sum_geometric_series(
     price_ratio: &Decimal,
     current_owned: &Decimal,
 ) -> Decimal {
-    price_start * price_ratio.pow(current_owned) * (ONE - price_ratio.pow(num_items))
-        / (ONE - price_ratio)
+    let actual_start = price_start * price_ratio.pow(current_owned);
+    if *price_ratio == ONE {
+        return actual_start * num_items;
+    }
+    actual_start * (ONE - price_ratio.pow(num_items)) / (ONE - price_ratio)
+}
+
+/// If you're willing to spend 'resourcesAvailable' and want to reach a level whose cost grows
+/// both linearly and exponentially (level n costs priceStart * n * priceRatio^n, priceRatio > 1),
+/// which level can you afford?
+///
+/// With r = ln(priceRatio), n * r * e^(n * r) = resources / priceStart * r,
+/// so n = W(resources / priceStart * r) / r, using the Lambert W function.
+/// A priceRatio of 1 leaves only the linear part; below 1, or for a priceStart of zero or less,
+/// prices don't grow and there is no highest level, which gives `INFINITY`.
+pub fn afford_linear_exponential(
+    resources_available: &Decimal,
+    price_start: &Decimal,
+    price_ratio: &Decimal,
+) -> Decimal {
+    if *price_start <= ZERO || *price_ratio < ONE {
+        return INFINITY;
+    }
+    if *price_ratio == ONE {
+        return (resources_available / price_start).floor();
+    }
+
+    let rate = price_ratio.ln();
+    let level = ((resources_available / price_start) * rate).lambert_w() / rate;
+    settle_count(level, resources_available, |level| {
+        price_start * level * price_ratio.pow(level)
+    })
+}
+
+/// How long does it take for a value which grows by a constant rate to reach the target?
+/// Zero if it is already there, and `INFINITY` if it doesn't grow.
+pub fn time_to_reach_linear(target: &Decimal, start: &Decimal, rate: &Decimal) -> Decimal {
+    if target <= start {
+        return ZERO;
+    }
+    if rate.mantissa <= 0.0 {
+        return INFINITY;
+    }
+    (target - start) / rate
+}
+
+/// How long does it take for a value which grows like
+/// `coefficients[0] + coefficients[1] * t + coefficients[2] * t^2 + ...`
+/// to reach the target? Zero if it is already there, and `INFINITY` if it doesn't grow.
+///
+/// The coefficients are expected to be non-negative, as they are for the currency
+/// at the end of a production chain. Up to t^2 the time is solved in closed form,
+/// higher powers are left to `time_to_reach`.
+pub fn time_to_reach_polynomial(target: &Decimal, coefficients: &[Decimal]) -> Decimal {
+    let start = coefficients.first().copied().unwrap_or(ZERO);
+    if *target <= start {
+        return ZERO;
+    }
+    let degree = coefficients
+        .iter()
+        .rposition(|coefficient| !coefficient.is_zero())
+        .unwrap_or(0);
+    match degree {
+        0 => INFINITY,
+        1 => time_to_reach_linear(target, &start, &coefficients[1]),
+        2 => {
+            // a * t^2 + b * t = target - start, with the root that doesn't cancel out
+            let (b, a) = (coefficients[1], coefficients[2]);
+            let missing = target - start;
+            let root = (b.sqr() + a * missing * 4.0).sqrt();
+            if b.is_negative() {
+                (root - b) / (a * TWO)
+            } else {
+                missing * TWO / (b + root)
+            }
+        }
+        _ => time_to_reach(target, |time| {
+            // Horner's method
+            coefficients
+                .iter()
+                .rev()
+                .fold(ZERO, |value, coefficient| value * time + coefficient)
+        }),
+    }
+}
+
+/// How long does it take for a value which grows like start * growth^t to reach the target?
+/// Zero if it is already there, and `INFINITY` if it doesn't grow.
+pub fn time_to_reach_exponential(target: &Decimal, start: &Decimal, growth: &Decimal) -> Decimal {
+    if target <= start {
+        return ZERO;
+    }
+    if start.mantissa <= 0.0 || *growth <= ONE {
+        return INFINITY;
+    }
+    (target / start).ln_decimal() / growth.ln_decimal()
+}
+
+/// How long does it take for a value which grows in any other way to reach the target?
+/// `value` gives the value after some time, and has to be non-decreasing.
+///
+/// The time is found by bisection, and it is the earliest time found at which the value
+/// has reached the target, so the target is never missed by rounding.
+/// Zero if the value is already there, and `INFINITY` if it can't be reached.
+///
+/// ```rust
+/// use break_infinity_extended::{time_to_reach, Decimal};
+///
+/// // t^3 reaches 1000 after 10 seconds
+/// let time = time_to_reach(&Decimal::new(1000.0), |t| t.powi(3));
+/// assert!(time.eq_tolerance(&Decimal::new(10.0), &Decimal::new(1e-14)));
+/// ```
+pub fn time_to_reach<F>(target: &Decimal, value: F) -> Decimal
+where
+    F: Fn(&Decimal) -> Decimal,
+{
+    let reached = |time: &Decimal| value(time) >= *target;
+    if reached(&ZERO) {
+        return ZERO;
+    }
+    match search_threshold(ZERO, ONE, false, reached) {
+        Ok((_, high)) => high,
+        Err(_) => INFINITY,
+    }
+}
+
+// Narrows down where a condition which only ever turns from false to true starts to hold,
+// starting from `low`, where it doesn't hold, and a larger `high`: first `high` grows until it
+// holds there, doubling and squaring once the values get big, then the bounds are bisected,
+// in the orders of magnitude while they are far apart. With `whole`, only whole numbers are tried.
+//
+// Gives the last bounds after `MAX_SEARCH_STEPS`, or the largest value for which the condition
+// doesn't hold as an error when no upper bound was found before the values became infinite.
+pub(crate) fn search_threshold<F>(
+    mut low: Decimal,
+    mut high: Decimal,
+    whole: bool,
+    holds: F,
+) -> Result<(Decimal, Decimal), Decimal>
+where
+    F: Fn(&Decimal) -> bool,
+{
+    let snap = |value: Decimal| if whole { value.round() } else { value };
+
+    let mut steps = 0;
+    while !holds(&high) {
+        low = high;
+        high = snap(if high.exponent < 16.0 {
+            high * TWO
+        } else {
+            high.sqr()
+        });
+        steps += 1;
+        if steps >= MAX_SEARCH_STEPS || high.is_infinite() {
+            return Err(low);
+        }
+    }
+
+    while steps < MAX_SEARCH_STEPS {
+        let middle = snap(if low.is_zero() {
+            high / TWO
+        } else if high > low * TWO {
+            (low * high).sqrt()
+        } else {
+            (low + high) / TWO
+        });
+        if middle <= low || middle >= high {
+            break;
+        }
+        if holds(&middle) {
+            high = middle;
+        } else {
+            low = middle;
+        }
+        steps += 1;
+    }
+    Ok((low, high))
+}
+
+/// How long does it take for a value which grows like (start + slope * t) * growth^t,
+/// such as a linearly growing production with an exponentially growing multiplier,
+/// to reach the target? Zero if it is already there, and `INFINITY` if it doesn't grow:
+/// with a negative slope, a growth below 1, a start of zero or less without a slope,
+/// or neither a slope nor a growth above 1.
+///
+/// With u = start + slope * t and c = ln(growth) / slope,
+/// c * u * e^(c * u) = c * target * e^(c * start), which is solved by the Lambert W function.
+pub fn time_to_reach_linear_exponential(
+    target: &Decimal,
+    start: &Decimal,
+    slope: &Decimal,
+    growth: &Decimal,
+) -> Decimal {
+    if target <= start {
+        return ZERO;
+    }
+    let rate = growth.ln();
+    if *slope < ZERO || rate < 0.0 {
+        return INFINITY;
+    }
+    if rate == 0.0 {
+        return time_to_reach_linear(target, start, slope);
+    }
+    if slope.is_zero() {
+        if *start <= ZERO {
+            return INFINITY;
+        }
+        return Decimal::new((target / start).ln() / rate);
+    }
+
+    let c = Decimal::new(rate) / slope;
+    let u = (c * target * exp_decimal(&(c * start))).lambert_w() / c;
+    (u - start) / slope
 }
 
 /// If you're willing to spend 'resourcesAvailable' and want to buy something with additively
 /// increasing cost each purchase (start at priceStart, add by priceAdd, already own currentOwned),
-/// how much of it can you buy?
+/// how much of it can you buy? If the purchases are free, there is no limit, which gives `INFINITY`.
 pub fn afford_arithmetic_series(
     resources_available: &Decimal,
     price_start: &Decimal,
@@ -44,9 +272,24 @@ pub fn afford_arithmetic_series(
     //  where a is actual_start, d is price_add and S is resources_available
     //  then floor it and you're done!
     let actual_start = price_start + (current_owned * price_add);
-    let b = actual_start - (price_add / TWO);
-    let b2 = b.pow(&TWO);
-    (b.neg() + ((b2 + ((price_add * resources_available) * TWO)).sqrt() / price_add)).floor()
+    if actual_start.is_zero() && price_add.is_zero() {
+        return INFINITY;
+    }
+    let count = if price_add.is_zero() {
+        (resources_available / actual_start).floor()
+    } else {
+        let b = actual_start - (price_add / TWO);
+        let root = (b.sqr() + ((price_add * resources_available) * TWO)).sqrt();
+        if b.is_negative() {
+            ((root - b) / price_add).floor()
+        } else {
+            // the same without the cancellation when d is small: n = 2S / (b + sqrt(b^2+2dS))
+            (resources_available * TWO / (b + root)).floor()
+        }
+    };
+    settle_count(count, resources_available, |count| {
+        sum_arithmetic_series(count, price_start, price_add, current_owned)
+    })
 }
 
 /// How much resource would it cost to buy (numItems) items if you already have currentOwned,
@@ -60,7 +303,7 @@ pub fn sum_arithmetic_series(
 ) -> Decimal {
     let actual_start = price_start + (current_owned * price_add); // (n/2)*(2*a+(n-1)*d)
 
-    num_items / TWO * (actual_start * TWO + (num_items - ONE) + num_items - ONE) * price_add
+    num_items / TWO * (actual_start * TWO + (num_items - ONE) * price_add)
 }
 
 /// When comparing two purchases that cost (resource) and increase your resource/sec by (deltaRpS),
@@ -75,3 +318,140 @@ pub fn efficiency_of_purchase(
 ) -> Decimal {
     cost / (current_rp_s + (cost / delta_rp_s))
 }
+
+// The closed forms for counts go through logarithms, roots or W, which are only exact up to
+// the last bits; those decide the rounding when the budget is right at a price, so the
+// neighbouring counts are checked against the cost of that many (and like all whole number
+// arithmetic on Decimals, the steps need rounding).
+pub(crate) fn settle_count<F>(count: Decimal, resources_available: &Decimal, cost: F) -> Decimal
+where
+    F: Fn(&Decimal) -> Decimal,
+{
+    if !count.is_finite() {
+        return count;
+    }
+    let count = count.floor().max(&ZERO);
+    let next = (count + ONE).round();
+    if cost(&next) <= *resources_available {
+        next
+    } else if count.mantissa > 0.0 && cost(&count) > *resources_available {
+        (count - ONE).round()
+    } else {
+        count
+    }
+}
+
+/// Sums up the values while keeping track of the error of every addition (Neumaier summation).
+///
+/// A plain sum drops any value which is more than `MAX_SIGNIFICANT_DIGITS` orders of magnitude
+/// smaller than the running total, so adding a million small contributions to a big total
+/// would not change it at all; here they are collected in the error term and added at the end.
+pub fn sum_compensated<I>(values: I) -> Decimal
+where
+    I: IntoIterator,
+    I::Item: Borrow<Decimal>,
+{
+    let mut sum = ZERO;
+    let mut compensation = ZERO;
+
+    for value in values {
+        let value = value.borrow();
+        let total = sum + value;
+        if sum.abs() >= value.abs() {
+            compensation += (sum - total) + value;
+        } else {
+            compensation += (value - total) + sum;
+        }
+        sum = total;
+    }
+
+    sum + compensation
+}
+
+/// The number of ways to arrange k out of n items in order, n! / (n - k)!.
+///
+/// Exact for integers while the product stays below 2^53; other values go through `ln_gamma`.
+/// Negative values have no result (NaN).
+pub fn permutations(n: &Decimal, k: &Decimal) -> Decimal {
+    if n.is_nan() || k.is_nan() || *n < ZERO || *k < ZERO {
+        return NAN;
+    }
+    if n.is_integer() && k.is_integer() {
+        if k > n {
+            return ZERO;
+        }
+        if k.to_number() <= MAX_FACTORIAL as f64 {
+            return falling_factorial(n, k.to_number() as usize);
+        }
+    }
+    exp_decimal(&ln_falling_factorial(n, k))
+}
+
+/// The number of ways to choose k out of n items, n! / (k! * (n - k)!).
+///
+/// Exact for integers while the intermediate products stay below 2^53; other values go through `ln_gamma`.
+/// Negative values have no result (NaN).
+pub fn binomial(n: &Decimal, k: &Decimal) -> Decimal {
+    if n.is_nan() || k.is_nan() || *n < ZERO || *k < ZERO {
+        return NAN;
+    }
+    if n.is_integer() && k.is_integer() {
+        if k > n {
+            return ZERO;
+        }
+        let k = k.min(&(n - k)).to_number();
+        if k <= MAX_FACTORIAL as f64 {
+            let k = k as usize;
+            return match small_binomial(n.to_number(), k) {
+                Some(exact) => Decimal::new(exact),
+                None => falling_factorial(n, k) / FACTORIALS[k],
+            };
+        }
+    }
+    exp_decimal(&(ln_falling_factorial(n, k) - (k + ONE).ln_gamma()))
+}
+
+// n * (n - 1) * ... * (n - k + 1), in f64 as long as that is exact
+fn falling_factorial(n: &Decimal, k: usize) -> Decimal {
+    let number = n.to_number();
+    let mut exact = 1.0;
+    let mut i = 0;
+    while i < k && exact * (number - i as f64) <= MAX_SAFE_INTEGER {
+        exact *= number - i as f64;
+        i += 1;
+    }
+
+    let mut product = Decimal::new(exact);
+    for j in i..k {
+        product *= n - j as f64;
+    }
+    product
+}
+
+// n over k in f64, if all intermediate products are exact
+fn small_binomial(n: f64, k: usize) -> Option<f64> {
+    let mut result = 1.0;
+    for i in 0..k {
+        let product = result * (n - i as f64);
+        if product > MAX_SAFE_INTEGER {
+            return None;
+        }
+        // n over i+1 = (n over i) * (n - i) / (i + 1) is an integer
+        result = product / (i + 1) as f64;
+    }
+    Some(result)
+}
+
+// ln(n! / (n - k)!)
+fn ln_falling_factorial(n: &Decimal, k: &Decimal) -> Decimal {
+    let rest = n - k;
+    let ratio = (k / n).to_number();
+    if ratio >= 1e-3 || rest < Decimal::new(10.0) {
+        return (n + ONE).ln_gamma() - (rest + ONE).ln_gamma();
+    }
+
+    // the difference of the two huge logarithms would cancel out most digits, so it is taken
+    // from Stirling's series directly: (rest + 0.5) * ln(n / rest) + k * (ln(n) - 1) + ...
+    let correction = 1.0 / (12.0 * n.to_number()) - 1.0 / (12.0 * rest.to_number());
+    (rest + 0.5) * -(-ratio).ln_1p() + k * (n.ln() - 1.0) + correction
+}
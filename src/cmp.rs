@@ -1,8 +1,13 @@
-use crate::Decimal;
-use std::cmp::Ordering::{self, *};
+use crate::{consts::*, Decimal};
+use core::{
+    cmp::Ordering::{self, *},
+    hash::{Hash, Hasher},
+};
 
-impl PartialOrd for Decimal {
-    fn partial_cmp(&self, decimal: &Self) -> Option<Ordering> {
+impl Decimal {
+    /// Compares two Decimals like `partial_cmp`, but usable in const context;
+    /// NaN isn't ordered with anything.
+    pub const fn compare(&self, decimal: &Decimal) -> Option<Ordering> {
         /*
         From smallest to largest:
         -Infinity
@@ -80,6 +85,12 @@ impl PartialOrd for Decimal {
             Some(Equal)
         }
     }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, decimal: &Self) -> Option<Ordering> {
+        self.compare(decimal)
+    }
 
     fn lt(&self, other: &Decimal) -> bool {
         self.partial_cmp(other)
@@ -111,3 +122,76 @@ impl PartialEq<Decimal> for Decimal {
 }
 
 impl Eq for Decimal {}
+
+// the bits of the Decimal with -0.0 and all NaNs mapped to a single representation each;
+// all zeroes and all NaNs hash the same, which is consistent with both `Eq` and `total_cmp`
+#[inline]
+fn canonical_bits(decimal: &Decimal) -> [u64; 2] {
+    if f64::is_nan(decimal.mantissa) || f64::is_nan(decimal.exponent) {
+        NAN.to_bits()
+    } else if decimal.mantissa == 0.0 {
+        ZERO.to_bits()
+    } else {
+        // + 0.0 turns -0.0 into 0.0
+        [
+            decimal.mantissa.to_bits(),
+            (decimal.exponent + 0.0).to_bits(),
+        ]
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        canonical_bits(self).hash(state);
+    }
+}
+
+/// A Decimal with a total order (see `Decimal::total_cmp`),
+/// so it can be used as a `BTreeMap` key, in a `HashSet`, or with `sort()` and `max()`.
+///
+/// Equality follows the total order as well: all NaNs are equal, so are 0 and -0.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct TotalDecimal(pub Decimal);
+
+impl From<Decimal> for TotalDecimal {
+    fn from(decimal: Decimal) -> TotalDecimal {
+        TotalDecimal(decimal)
+    }
+}
+
+impl From<TotalDecimal> for Decimal {
+    fn from(total: TotalDecimal) -> Decimal {
+        total.0
+    }
+}
+
+impl PartialEq for TotalDecimal {
+    fn eq(&self, other: &TotalDecimal) -> bool {
+        self.0.total_cmp(&other.0) == Equal
+    }
+}
+
+impl Eq for TotalDecimal {}
+
+impl PartialOrd for TotalDecimal {
+    fn partial_cmp(&self, other: &TotalDecimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TotalDecimal {
+    fn cmp(&self, other: &TotalDecimal) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for TotalDecimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
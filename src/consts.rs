@@ -14,19 +14,20 @@ pub(crate) const ALMOST_ZERO_EXP_LIMIT: f64 = NEG_EXP_LIMIT + 1e292;
 pub const ROUND_TOLERANCE: f64 = f64::EPSILON;
 
 bie_proc_macros::insert_consts_and_cache_for_powers_of_ten!();
+bie_proc_macros::insert_factorials!();
 
 pub const NAN: Decimal = Decimal {
     mantissa: f64::NAN,
     exponent: f64::NAN,
 };
 
-// "true" infinity; internal use only
+/// "true" infinity; prefer `MAX`, which is what infinite f64 values and `"Infinity"` turn into
 pub const INFINITY: Decimal = Decimal {
     mantissa: 1.0,
     exponent: f64::INFINITY,
 };
 
-// "true" -infinity; internal use only
+/// "true" -infinity; prefer `MIN`, which is what infinite f64 values and `"-Infinity"` turn into
 pub const NEG_INFINITY: Decimal = Decimal {
     mantissa: -1.0,
     exponent: f64::INFINITY,
@@ -57,7 +58,8 @@ pub const TEN: Decimal = Decimal {
     exponent: 1.0,
 };
 
-/// The largest positive number that can be represented by a Decimal
+/// The largest positive number that can be represented by a Decimal; it is treated as infinity
+/// (`Decimal::new(f64::INFINITY)` returns it, it displays as `Infinity` and `is_infinite()` is true);
 /// theoretically there is some space left both in mantissa and exponent,
 /// but for practical and compatibility reasons we use the same values as in `break_infinity`
 pub const MAX: Decimal = Decimal {
@@ -65,7 +67,8 @@ pub const MAX: Decimal = Decimal {
     exponent: EXP_LIMIT,
 };
 
-/// The smallest negative number that can be represented by a Decimal
+/// The smallest negative number that can be represented by a Decimal; it is treated as -infinity
+/// (see `MAX`);
 /// theoretically there is some space left both in mantissa and exponent,
 /// but for practical and compatibility reasons we use the same values as in `break_infinity`
 pub const MIN: Decimal = Decimal {
@@ -73,6 +76,18 @@ pub const MIN: Decimal = Decimal {
     exponent: EXP_LIMIT,
 };
 
+/// The largest finite Decimal, just below `MAX`; the upper bound of `num_traits::Bounded`
+pub const MAX_FINITE: Decimal = Decimal {
+    mantissa: 9.999999999999998,
+    exponent: f64::from_bits(EXP_LIMIT.to_bits() - 1),
+};
+
+/// The smallest finite Decimal, just above `MIN`; the lower bound of `num_traits::Bounded`
+pub const MIN_FINITE: Decimal = Decimal {
+    mantissa: -9.999999999999998,
+    exponent: f64::from_bits(EXP_LIMIT.to_bits() - 1),
+};
+
 /// The smallest positive number that can be represented by a Decimal;
 /// the absolute value of the exponent has to be slightly smaller than EXP_LIMIT
 pub const ALMOST_ZERO: Decimal = Decimal {
@@ -88,16 +103,16 @@ pub const ALMOST_NEGATIVE_ZERO: Decimal = Decimal {
 };
 
 pub const PI: Decimal = Decimal {
-    mantissa: std::f64::consts::PI,
+    mantissa: core::f64::consts::PI,
     exponent: 0.0,
 };
 
 pub const TAU: Decimal = Decimal {
-    mantissa: std::f64::consts::TAU,
+    mantissa: core::f64::consts::TAU,
     exponent: 0.0,
 };
 
 pub const E: Decimal = Decimal {
-    mantissa: std::f64::consts::E,
+    mantissa: core::f64::consts::E,
     exponent: 0.0,
 };
@@ -0,0 +1,320 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::F64Ext;
+use crate::{
+    calculations::{search_threshold, settle_count, sum_compensated},
+    consts::*,
+    utils::exp_decimal,
+    Decimal,
+};
+use alloc::vec::Vec;
+
+/// Counts up to which sums without a closed form are added up term by term.
+const MAX_SUMMED_TERMS: u64 = 1_000;
+
+/// Terms after which a sum of superexponential costs stops, counting down from the most expensive one.
+const MAX_TAIL_TERMS: u64 = 10_000;
+
+/// How the cost of a purchase depends on the number already owned.
+///
+/// Purchases are counted from zero: `cost_of(n)` is the price of the purchase
+/// that raises the amount owned from n to n + 1. Counts are whole numbers,
+/// and all parameters are expected to keep the costs positive and non-decreasing.
+///
+/// ```rust
+/// use break_infinity_extended::{CostModel, Decimal};
+///
+/// // 10 * 2^n for the first 100 purchases, then the ratio itself grows by 1.1 per purchase
+/// let model = CostModel::Piecewise(vec![
+///     (Decimal::new(0.0), CostModel::Exponential { base: Decimal::new(10.0), ratio: Decimal::new(2.0) }),
+///     (
+///         Decimal::new(100.0),
+///         CostModel::Superexponential {
+///             base: Decimal::new(10.0) * Decimal::new(2.0).powi(100),
+///             ratio: Decimal::new(2.0),
+///             scaling: Decimal::new(1.1),
+///         },
+///     ),
+/// ]);
+/// assert_eq!(model.cost_of(&Decimal::new(3.0)), Decimal::new(80.0));
+/// assert_eq!(model.total_cost(&Decimal::new(0.0), &Decimal::new(4.0)), Decimal::new(150.0));
+/// assert_eq!(model.max_affordable(&Decimal::new(0.0), &Decimal::new(150.0)), Decimal::new(4.0));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum CostModel {
+    /// base + increment * n
+    Linear { base: Decimal, increment: Decimal },
+    /// base * ratio^n, for a ratio of at least one
+    Exponential { base: Decimal, ratio: Decimal },
+    /// base * (n + 1)^power, for a non-negative power
+    Polynomial { base: Decimal, power: f64 },
+    /// base * ratio^n * scaling^(n * (n - 1) / 2): the ratio itself is multiplied by scaling
+    /// on every purchase, as in the cost scaling of Antimatter Dimensions
+    Superexponential {
+        base: Decimal,
+        ratio: Decimal,
+        scaling: Decimal,
+    },
+    /// Different models for different amounts owned: each model applies from its start on
+    /// (sorted ascending, starting at zero) and counts its purchases from there.
+    /// Without any models, there are no costs and every result is NaN.
+    Piecewise(Vec<(Decimal, CostModel)>),
+}
+
+impl CostModel {
+    /// The price of the purchase which raises the amount owned from n to n + 1.
+    pub fn cost_of(&self, n: &Decimal) -> Decimal {
+        match self {
+            CostModel::Linear { base, increment } => base + increment * n,
+            CostModel::Exponential { base, ratio } => base * ratio.pow(n),
+            CostModel::Polynomial { base, power } => base * (n + ONE).powf(*power),
+            CostModel::Superexponential {
+                base,
+                ratio,
+                scaling,
+            } => base * ratio.pow(n) * scaling.pow(&(n * (n - ONE) / TWO)),
+            CostModel::Piecewise(segments) => match segment_index(segments, n) {
+                Some(index) => {
+                    let (start, model) = &segments[index];
+                    model.cost_of(&(n - start).round())
+                }
+                None => NAN,
+            },
+        }
+    }
+
+    /// The price of buying `count` more when `owned` are already owned.
+    ///
+    /// Linear and exponential costs have closed forms. Polynomial costs are summed up
+    /// for the first thousand purchases and approximated by the Euler–Maclaurin formula after that,
+    /// which is exact for the integer powers 0 to 3 and close, but not exact, for any other power;
+    /// superexponential costs are summed from the most expensive purchase down,
+    /// until the cheaper ones can't change the result anymore. Superexponential costs without
+    /// scaling are exponential ones; with a scaling so close to one that more than ten thousand
+    /// purchases still change the sum, it has no result here and is NaN.
+    pub fn total_cost(&self, owned: &Decimal, count: &Decimal) -> Decimal {
+        if count.mantissa <= 0.0 {
+            return ZERO;
+        }
+        match self {
+            CostModel::Linear { base, increment } => {
+                count * (base + increment * (owned + (count - ONE) / TWO))
+            }
+            CostModel::Exponential { base, ratio } => {
+                if *ratio == ONE {
+                    return base * count;
+                }
+                // base * ratio^owned * (ratio^count - 1) / (ratio - 1)
+                let power = count * ratio.ln_decimal();
+                let growth = if power.abs() < ONE {
+                    exp_m1_decimal(&power)
+                } else {
+                    ratio.pow(count) - ONE
+                };
+                self.cost_of(owned) * growth / (ratio - ONE)
+            }
+            CostModel::Polynomial { base, power } => base * sum_of_powers(owned, count, *power),
+            CostModel::Superexponential {
+                base,
+                ratio,
+                scaling,
+            } => {
+                if *scaling == ONE {
+                    let exponential = CostModel::Exponential {
+                        base: *base,
+                        ratio: *ratio,
+                    };
+                    return exponential.total_cost(owned, count);
+                }
+                let mut n = (owned + count - ONE).round();
+                let mut total = ZERO;
+                for _ in 0..MAX_TAIL_TERMS {
+                    let cost = self.cost_of(&n);
+                    total += cost;
+                    let next = (n - ONE).round();
+                    if cost.is_nan() || next < *owned || next == n || cost < total * f64::EPSILON {
+                        return total;
+                    }
+                    n = next;
+                }
+                // none of the purchases left costs more than the next one
+                let left = (n - owned + ONE).round() * self.cost_of(&n);
+                if left < total * f64::EPSILON {
+                    total
+                } else {
+                    NAN
+                }
+            }
+            CostModel::Piecewise(segments) => {
+                let mut position = *owned;
+                let mut remaining = *count;
+                let mut total = ZERO;
+                while remaining.mantissa > 0.0 {
+                    let Some(index) = segment_index(segments, &position) else {
+                        return NAN;
+                    };
+                    let (start, model) = &segments[index];
+                    let bought = match segments.get(index + 1) {
+                        Some((end, _)) => remaining.min(&(end - position).round()),
+                        None => remaining,
+                    };
+                    total += model.total_cost(&(position - start).round(), &bought);
+                    position = (position + bought).round();
+                    remaining = (remaining - bought).round();
+                }
+                total
+            }
+        }
+    }
+
+    /// The largest count for which `total_cost(owned, count)` stays within the budget.
+    ///
+    /// Linear and exponential costs are solved in closed form, everything else by a bounded
+    /// binary search, which stops at counts whose total is NaN; either way the result is
+    /// a whole number, exact while it fits into `MAX_SAFE_INTEGER`. The one exception are polynomial costs with powers other than 0 to 3
+    /// beyond the first thousand purchases: there `total_cost` is approximated, so a budget right
+    /// at a total may give a count one off.
+    ///
+    /// If the budget never runs out, because the purchases are free or the costs stay within it
+    /// even for counts beyond a Decimal, there is no limit, which gives `INFINITY`.
+    pub fn max_affordable(&self, owned: &Decimal, budget: &Decimal) -> Decimal {
+        if budget.is_nan() || budget < &self.cost_of(owned) {
+            return ZERO;
+        }
+        if self.is_free() {
+            return INFINITY;
+        }
+        match self {
+            CostModel::Linear { base, increment } => {
+                if increment.is_zero() {
+                    return self.settle(owned, budget, &(budget / base));
+                }
+                // count * (cost_of(owned) - increment / 2) + count^2 * increment / 2 = budget
+                let b = self.cost_of(owned) - increment / TWO;
+                let root = (b.sqr() + increment * budget * TWO).sqrt();
+                let count = if b.mantissa >= 0.0 {
+                    budget * TWO / (b + root)
+                } else {
+                    (root - b) / increment
+                };
+                self.settle(owned, budget, &count)
+            }
+            CostModel::Exponential { base, ratio } => {
+                if *ratio == ONE {
+                    return self.settle(owned, budget, &(budget / base));
+                }
+                // ratio^count = 1 + budget * (ratio - 1) / cost_of(owned)
+                let scaled = budget * (ratio - ONE) / self.cost_of(owned);
+                let ln = if scaled.exponent < 300.0 {
+                    Decimal::new(scaled.to_number().ln_1p())
+                } else {
+                    scaled.ln_decimal()
+                };
+                self.settle(owned, budget, &(ln / ratio.ln_decimal()))
+            }
+            CostModel::Piecewise(segments) => {
+                let mut position = *owned;
+                let mut budget = *budget;
+                let mut count = ZERO;
+                loop {
+                    let Some(index) = segment_index(segments, &position) else {
+                        return NAN;
+                    };
+                    let (start, model) = &segments[index];
+                    if let Some((end, _)) = segments.get(index + 1) {
+                        let span = (end - position).round();
+                        let cost = model.total_cost(&(position - start).round(), &span);
+                        if cost <= budget {
+                            budget -= cost;
+                            count = (count + span).round();
+                            position = *end;
+                            continue;
+                        }
+                    }
+                    return (count + model.max_affordable(&(position - start).round(), &budget))
+                        .round();
+                }
+            }
+            _ => self.search_affordable(owned, budget),
+        }
+    }
+
+    // whether every purchase costs nothing; piecewise costs leave that to their last model
+    fn is_free(&self) -> bool {
+        match self {
+            CostModel::Linear { base, increment } => base.is_zero() && increment.is_zero(),
+            CostModel::Exponential { base, .. }
+            | CostModel::Polynomial { base, .. }
+            | CostModel::Superexponential { base, .. } => base.is_zero(),
+            CostModel::Piecewise(_) => false,
+        }
+    }
+
+    fn settle(&self, owned: &Decimal, budget: &Decimal, count: &Decimal) -> Decimal {
+        settle_count(*count, budget, |count| self.total_cost(owned, count))
+    }
+
+    fn search_affordable(&self, owned: &Decimal, budget: &Decimal) -> Decimal {
+        // a total without a result (NaN) doesn't fit either
+        let fits = |count: &Decimal| self.total_cost(owned, count) <= *budget;
+        match search_threshold(ONE, TWO, true, |count| !fits(count)) {
+            Ok((low, _)) => low,
+            Err(_) => INFINITY,
+        }
+    }
+}
+
+// the index of the segment the given amount owned falls into, if there are any segments
+fn segment_index(segments: &[(Decimal, CostModel)], n: &Decimal) -> Option<usize> {
+    if segments.is_empty() {
+        return None;
+    }
+    Some(
+        segments
+            .iter()
+            .rposition(|(start, _)| start <= n)
+            .unwrap_or(0),
+    )
+}
+
+// e^power - 1, without cancellation for small powers
+fn exp_m1_decimal(power: &Decimal) -> Decimal {
+    if power.abs() < ONE {
+        Decimal::new(power.to_number().exp_m1())
+    } else {
+        exp_decimal(power) - ONE
+    }
+}
+
+// the sum of k^power for k from owned + 1 to owned + count
+fn sum_of_powers(owned: &Decimal, count: &Decimal, power: f64) -> Decimal {
+    let summed = if *owned < Decimal::new(MAX_SUMMED_TERMS as f64) {
+        (Decimal::new(MAX_SUMMED_TERMS as f64) - owned)
+            .round()
+            .min(count)
+    } else {
+        ZERO
+    };
+    let head = sum_compensated(
+        (1..=summed.to_number() as u64)
+            .map(|k| (owned + Decimal::new(k as f64)).round().powf(power)),
+    );
+    if summed == *count {
+        return head;
+    }
+
+    // Euler–Maclaurin: the integral from a to b, the mean of both ends and the first correction
+    let a = (owned + summed + ONE).round();
+    let b = (owned + count).round();
+    // b - a straight from the counts, the difference of two big values would lose the digits
+    let ratio = ((count - summed - ONE).round() / a).to_number();
+    let integral = if f64::is_finite(ratio) {
+        // (b^(power + 1) - a^(power + 1)) / (power + 1) without the cancellation
+        a.powf(power + 1.0) * exp_m1_decimal(&Decimal::new((power + 1.0) * ratio.ln_1p()))
+            / (power + 1.0)
+    } else {
+        b.powf(power + 1.0) / (power + 1.0)
+    };
+    let ends = (a.powf(power) + b.powf(power)) / TWO;
+    let correction = (b.powf(power - 1.0) - a.powf(power - 1.0)) * (power / 12.0);
+    head + integral + ends + correction
+}
@@ -0,0 +1,186 @@
+//! A compact, variable-length binary encoding of Decimals, for saves with many values.
+//!
+//! Every value starts with a tag byte:
+//!
+//! | tag | form                                                           | bytes  |
+//! |-----|----------------------------------------------------------------|--------|
+//! | 0-6 | `ZERO`, `ONE`, `NAN`, `MAX`, `MIN`, `INFINITY`, `NEG_INFINITY` | 1      |
+//! | 7   | the mantissa as an `f32`, then the exponent as a varint        | 6-15   |
+//! | 8   | the mantissa as an `f64`, then the exponent as a varint        | 10-19  |
+//! | 9   | the mantissa and the exponent as `f64`s, like `to_le_bytes`    | 17     |
+//!
+//! Floats are little-endian, and exponents are zigzag-encoded LEB128 varints, so exponents
+//! from -64 to 63 take a single byte. Each value is encoded in the shortest form which gives back
+//! the very same bits, so decoding always returns exactly what was encoded.
+
+use crate::{consts::*, error::DecodeDecimalError, Decimal};
+use alloc::vec::Vec;
+
+const SPECIAL_VALUES: [Decimal; 7] = [ZERO, ONE, NAN, MAX, MIN, INFINITY, NEG_INFINITY];
+const SHORT_TAG: u8 = 7;
+const LONG_TAG: u8 = 8;
+const RAW_TAG: u8 = 9;
+
+impl Decimal {
+    /// Appends the compact encoding of the Decimal to the buffer.
+    pub fn encode_compact(&self, buffer: &mut Vec<u8>) {
+        let bits = self.to_bits();
+        if let Some(tag) = SPECIAL_VALUES.iter().position(|v| v.to_bits() == bits) {
+            buffer.push(tag as u8);
+            return;
+        }
+
+        let exponent = self.exponent as i64;
+        if (exponent as f64).to_bits() != bits[1] {
+            buffer.push(RAW_TAG);
+            buffer.extend_from_slice(&self.to_le_bytes());
+            return;
+        }
+
+        let short = self.mantissa as f32;
+        if (short as f64).to_bits() == bits[0] {
+            buffer.push(SHORT_TAG);
+            buffer.extend_from_slice(&short.to_le_bytes());
+        } else {
+            buffer.push(LONG_TAG);
+            buffer.extend_from_slice(&self.mantissa.to_le_bytes());
+        }
+        write_varint(buffer, zigzag(exponent));
+    }
+
+    /// Returns the compact encoding of the Decimal, between 1 and 19 bytes.
+    ///
+    /// ```rust
+    /// use break_infinity_extended::{consts::ONE, from_mantissa_exponent, Decimal};
+    ///
+    /// assert_eq!(ONE.to_compact_bytes().len(), 1);
+    /// // 1.5 fits into an f32, and the exponent into two bytes
+    /// let value = from_mantissa_exponent(1.5, 300.0);
+    /// let bytes = value.to_compact_bytes();
+    /// assert_eq!(bytes.len(), 7);
+    /// assert_eq!(Decimal::decode_compact(&bytes), Ok((value, 7)));
+    /// ```
+    pub fn to_compact_bytes(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        self.encode_compact(&mut buffer);
+        buffer
+    }
+
+    /// Decodes a Decimal from the start of the bytes;
+    /// returns it along with the number of bytes it took up.
+    pub fn decode_compact(bytes: &[u8]) -> Result<(Decimal, usize), DecodeDecimalError> {
+        let mut reader = Reader { bytes, offset: 0 };
+        let value = reader.decimal()?;
+        Ok((value, reader.offset))
+    }
+}
+
+/// Encodes the Decimals one after another, behind their count as a varint.
+///
+/// ```rust
+/// use break_infinity_extended::{decode_slice, encode_slice, Decimal};
+///
+/// let values = [Decimal::new(0.0), Decimal::new(1.0), Decimal::new(1.5e300)];
+/// let bytes = encode_slice(&values);
+/// assert!(bytes.len() < 16 * values.len());
+/// assert_eq!(decode_slice(&bytes), Ok((values.to_vec(), bytes.len())));
+/// ```
+pub fn encode_slice(values: &[Decimal]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(values.len() * 8);
+    write_varint(&mut buffer, values.len() as u64);
+    for value in values {
+        value.encode_compact(&mut buffer);
+    }
+    buffer
+}
+
+/// Decodes Decimals encoded by `encode_slice` from the start of the bytes;
+/// returns them along with the number of bytes they took up.
+pub fn decode_slice(bytes: &[u8]) -> Result<(Vec<Decimal>, usize), DecodeDecimalError> {
+    let mut reader = Reader { bytes, offset: 0 };
+    let count = reader.varint()?;
+    // every value takes at least a byte, which keeps corrupt counts from allocating too much
+    let capacity = (count as usize).min(bytes.len() - reader.offset);
+    let mut values = Vec::with_capacity(capacity);
+    for _ in 0..count {
+        values.push(reader.decimal()?);
+    }
+    Ok((values, reader.offset))
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn decimal(&mut self) -> Result<Decimal, DecodeDecimalError> {
+        let start = self.offset;
+        let [tag] = self.take()?;
+        match tag {
+            SHORT_TAG => {
+                let mantissa = f32::from_le_bytes(self.take()?) as f64;
+                Ok(Decimal {
+                    mantissa,
+                    exponent: unzigzag(self.varint()?) as f64,
+                })
+            }
+            LONG_TAG => {
+                let mantissa = f64::from_le_bytes(self.take()?);
+                Ok(Decimal {
+                    mantissa,
+                    exponent: unzigzag(self.varint()?) as f64,
+                })
+            }
+            RAW_TAG => Ok(Decimal::from_le_bytes(&self.take()?)),
+            _ => SPECIAL_VALUES
+                .get(tag as usize)
+                .copied()
+                .ok_or(DecodeDecimalError::InvalidTag { offset: start, tag }),
+        }
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], DecodeDecimalError> {
+        let bytes = self.bytes.get(self.offset..self.offset + N).ok_or(
+            DecodeDecimalError::UnexpectedEnd {
+                offset: self.bytes.len(),
+            },
+        )?;
+        self.offset += N;
+        Ok(bytes.try_into().expect("take returns exactly N bytes"))
+    }
+
+    fn varint(&mut self) -> Result<u64, DecodeDecimalError> {
+        let start = self.offset;
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let [byte] = self.take()?;
+            let bits = (byte & 0x7f) as u64;
+            // the tenth byte only has room for the highest bit
+            if shift == 63 && bits > 1 {
+                return Err(DecodeDecimalError::InvalidVarint { offset: start });
+            }
+            value |= bits << shift;
+            if byte < 0x80 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeDecimalError::InvalidVarint { offset: start })
+    }
+}
@@ -0,0 +1,126 @@
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+// defined next to the parser, which the `dec!` macro shares
+pub use crate::parts::ParseDecimalError;
+
+impl ParseDecimalError {
+    /// Returns the byte offset at which parsing failed, if any.
+    pub fn offset(&self) -> Option<usize> {
+        match *self {
+            ParseDecimalError::Empty | ParseDecimalError::UnsupportedRadix { .. } => None,
+            ParseDecimalError::InvalidMantissa { offset }
+            | ParseDecimalError::InvalidExponent { offset }
+            | ParseDecimalError::ExponentOutOfRange { offset }
+            | ParseDecimalError::TrailingCharacters { offset } => Some(offset),
+        }
+    }
+
+    /// Moves the offset of an error from parsing a part of the input by the position of that part;
+    /// an empty part is an invalid mantissa.
+    pub(crate) fn shifted(self, by: usize) -> ParseDecimalError {
+        match self {
+            ParseDecimalError::Empty => ParseDecimalError::InvalidMantissa { offset: by },
+            ParseDecimalError::InvalidMantissa { offset } => ParseDecimalError::InvalidMantissa {
+                offset: offset + by,
+            },
+            ParseDecimalError::InvalidExponent { offset } => ParseDecimalError::InvalidExponent {
+                offset: offset + by,
+            },
+            ParseDecimalError::ExponentOutOfRange { offset } => {
+                ParseDecimalError::ExponentOutOfRange {
+                    offset: offset + by,
+                }
+            }
+            ParseDecimalError::TrailingCharacters { offset } => {
+                ParseDecimalError::TrailingCharacters {
+                    offset: offset + by,
+                }
+            }
+            ParseDecimalError::UnsupportedRadix { .. } => self,
+        }
+    }
+}
+
+impl Error for ParseDecimalError {}
+
+/// An error which can be returned when creating a Decimal from a mantissa and an exponent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryFromPartsError {
+    /// The mantissa is NaN or infinite.
+    NonFiniteMantissa,
+    /// The exponent is NaN or infinite.
+    NonFiniteExponent,
+    /// After normalization, the exponent is above `EXP_LIMIT`.
+    ExponentOutOfRange,
+}
+
+impl Display for TryFromPartsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TryFromPartsError::NonFiniteMantissa => write!(f, "mantissa is not finite"),
+            TryFromPartsError::NonFiniteExponent => write!(f, "exponent is not finite"),
+            TryFromPartsError::ExponentOutOfRange => {
+                write!(f, "exponent out of range after normalization")
+            }
+        }
+    }
+}
+
+impl Error for TryFromPartsError {}
+
+/// An error which is returned when converting a LayeredDecimal beyond the range of a Decimal into one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TryFromLayeredError;
+
+impl Display for TryFromLayeredError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "value is out of the range of a Decimal")
+    }
+}
+
+impl Error for TryFromLayeredError {}
+
+/// An error which can be returned when decoding Decimals from their compact encoding.
+///
+/// All offsets are byte offsets into the input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeDecimalError {
+    /// The input ended in the middle of a value.
+    UnexpectedEnd { offset: usize },
+    /// A value started with a byte which isn't the tag of any form.
+    InvalidTag { offset: usize, tag: u8 },
+    /// An exponent or length is longer than any 64-bit integer.
+    InvalidVarint { offset: usize },
+}
+
+impl DecodeDecimalError {
+    /// Returns the byte offset at which decoding failed.
+    pub fn offset(&self) -> usize {
+        match *self {
+            DecodeDecimalError::UnexpectedEnd { offset }
+            | DecodeDecimalError::InvalidTag { offset, .. }
+            | DecodeDecimalError::InvalidVarint { offset } => offset,
+        }
+    }
+}
+
+impl Display for DecodeDecimalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeDecimalError::UnexpectedEnd { offset } => {
+                write!(f, "unexpected end of input at byte {offset}")
+            }
+            DecodeDecimalError::InvalidTag { offset, tag } => {
+                write!(f, "invalid tag {tag} at byte {offset}")
+            }
+            DecodeDecimalError::InvalidVarint { offset } => {
+                write!(f, "invalid varint at byte {offset}")
+            }
+        }
+    }
+}
+
+impl Error for DecodeDecimalError {}
@@ -0,0 +1,147 @@
+//! The `f64` methods std provides on top of core, through `libm` for builds without std.
+//!
+//! The methods have the same names as std's, so code using them doesn't change;
+//! modules only import `F64Ext` when std isn't there (which it always is for tests).
+
+pub(crate) trait F64Ext {
+    fn floor(self) -> f64;
+    fn ceil(self) -> f64;
+    fn round(self) -> f64;
+    fn trunc(self) -> f64;
+    fn fract(self) -> f64;
+    fn rem_euclid(self, rhs: f64) -> f64;
+    fn div_euclid(self, rhs: f64) -> f64;
+    fn sqrt(self) -> f64;
+    fn powi(self, n: i32) -> f64;
+    fn powf(self, n: f64) -> f64;
+    fn exp(self) -> f64;
+    fn exp_m1(self) -> f64;
+    fn ln(self) -> f64;
+    fn ln_1p(self) -> f64;
+    fn log10(self) -> f64;
+    fn sin(self) -> f64;
+    fn cos(self) -> f64;
+    fn tan(self) -> f64;
+    fn asin(self) -> f64;
+    fn acos(self) -> f64;
+    fn atan(self) -> f64;
+    fn atan2(self, other: f64) -> f64;
+    fn asinh(self) -> f64;
+    fn acosh(self) -> f64;
+    fn atanh(self) -> f64;
+}
+
+impl F64Ext for f64 {
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+
+    fn ceil(self) -> f64 {
+        libm::ceil(self)
+    }
+
+    fn round(self) -> f64 {
+        libm::round(self)
+    }
+
+    fn trunc(self) -> f64 {
+        libm::trunc(self)
+    }
+
+    fn fract(self) -> f64 {
+        self - libm::trunc(self)
+    }
+
+    fn rem_euclid(self, rhs: f64) -> f64 {
+        let remainder = self % rhs;
+        if remainder < 0.0 {
+            remainder + rhs.abs()
+        } else {
+            remainder
+        }
+    }
+
+    fn div_euclid(self, rhs: f64) -> f64 {
+        let quotient = libm::trunc(self / rhs);
+        if self % rhs < 0.0 {
+            return if rhs > 0.0 {
+                quotient - 1.0
+            } else {
+                quotient + 1.0
+            };
+        }
+        quotient
+    }
+
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n as f64)
+    }
+
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    fn exp_m1(self) -> f64 {
+        libm::expm1(self)
+    }
+
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+
+    fn ln_1p(self) -> f64 {
+        libm::log1p(self)
+    }
+
+    fn log10(self) -> f64 {
+        libm::log10(self)
+    }
+
+    fn sin(self) -> f64 {
+        libm::sin(self)
+    }
+
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+
+    fn tan(self) -> f64 {
+        libm::tan(self)
+    }
+
+    fn asin(self) -> f64 {
+        libm::asin(self)
+    }
+
+    fn acos(self) -> f64 {
+        libm::acos(self)
+    }
+
+    fn atan(self) -> f64 {
+        libm::atan(self)
+    }
+
+    fn atan2(self, other: f64) -> f64 {
+        libm::atan2(self, other)
+    }
+
+    fn asinh(self) -> f64 {
+        libm::asinh(self)
+    }
+
+    fn acosh(self) -> f64 {
+        libm::acosh(self)
+    }
+
+    fn atanh(self) -> f64 {
+        libm::atanh(self)
+    }
+}
@@ -1,36 +1,53 @@
-use crate::{consts, impl_from, utils::*, Decimal};
+use crate::{error::ParseDecimalError, impl_from, parts, utils::*, Decimal};
+use alloc::string::String;
+use core::str::FromStr;
 
+impl FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    /// Parses a Decimal from strings like `123.45`, `-1.5e1000`, `1E+5`, `NaN` or `Infinity`.
+    ///
+    /// Leading and trailing whitespace is ignored; `Infinity` and `-Infinity` become `MAX` and `MIN`.
+    fn from_str(string: &str) -> Result<Decimal, ParseDecimalError> {
+        let (mantissa, exponent) = parts::parse(string)?;
+        Ok(from_mantissa_exponent_no_normalize(mantissa, exponent))
+    }
+}
+
+/// Parses a Decimal, panicking on invalid input.
+///
+/// Because of this impl, `Decimal::try_from(&str)` also panics instead of returning an error;
+/// use `str::parse` (`FromStr`) for a fallible conversion.
 impl From<&str> for Decimal {
     /// Creates a new instance of Decimal from the given &str.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid number; use `str::parse` for a fallible conversion.
     fn from(string: &str) -> Decimal {
-        if string == "NaN" {
-            return consts::NAN;
-        };
-        if string == "Infinity" {
-            return consts::MAX;
-        };
-        if string == "-Infinity" {
-            return consts::MIN;
-        };
-        if let Some((mantissa, exponent)) = string.split_once('e') {
-            return normalize_mantissa_and_exponent(
-                mantissa.parse().unwrap(),
-                exponent.parse().unwrap(),
-            );
-        };
-        Decimal::new(string.parse().unwrap())
+        string.parse().unwrap()
     }
 }
 
+/// Parses a Decimal, panicking on invalid input; see `From<&str>`.
 impl From<String> for Decimal {
-    /// Creates a new instance of Decimal from the given &String.
+    /// Creates a new instance of Decimal from the given String.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid number; use `str::parse` for a fallible conversion.
     fn from(string: String) -> Decimal {
         Decimal::from(string.as_str())
     }
 }
 
+/// Parses a Decimal, panicking on invalid input; see `From<&str>`.
 impl From<&String> for Decimal {
     /// Creates a new instance of Decimal from the given &String.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid number; use `str::parse` for a fallible conversion.
     fn from(string: &String) -> Decimal {
         Decimal::from(string.as_str())
     }
@@ -0,0 +1,864 @@
+//! A number type which stacks Decimals in layers of powers of ten, in the style of
+//! break_eternity.js (<https://github.com/Patashu/break_eternity.js>); it reaches
+//! 10^^(2^64) instead of Decimal's 1e1.79e308.
+//!
+//! ```rust
+//! use break_infinity_extended::{Decimal, LayeredDecimal};
+//!
+//! let tower: LayeredDecimal = "10^^5".parse().unwrap();
+//! assert_eq!(tower, "ee1e1e10".parse().unwrap());
+//! assert_eq!(tower.layer(), 2);
+//! assert_eq!(tower.slog(10.0), 5.0);
+//!
+//! let small = LayeredDecimal::from(Decimal::new(1e100));
+//! assert_eq!(Decimal::try_from(small.sqrt()), Ok(Decimal::new(1e50)));
+//! ```
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::F64Ext;
+use crate::{consts::*, error::*, ops::*, parts::parse_special, utils::*, Decimal};
+use alloc::borrow::ToOwned;
+use core::{
+    cmp::Ordering::{self, *},
+    f64::consts::{LN_10, LOG10_E},
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// Iterations after which a power tower (or a tower of power towers) is assumed to have converged.
+const MAX_ITERATIONS: u64 = 10_000;
+
+/// Layers up to which `Display` writes one `e` per layer instead of `(e^N)`.
+const MAX_DISPLAYED_LAYERS: u64 = 5;
+
+/// The smallest value with a real super square root, (1/e)^(1/e).
+const SSQRT_MIN: f64 = 0.6922006275553464;
+
+/// A number of the form `sign * 10^10^...^magnitude`, with `layer` tens in the tower.
+///
+/// The magnitude is a Decimal, so layer 0 holds every finite Decimal as is, which makes
+/// `From<Decimal>` lossless. From layer 1 on, the absolute value of the magnitude is at least
+/// `EXP_LIMIT` and a negative magnitude stands for the reciprocal (`e-1e400` is 10^-1e400).
+/// Values always stay normalized like this, so every value has exactly one representation.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayeredDecimal {
+    sign: i8,
+    layer: u64,
+    magnitude: Decimal,
+}
+
+impl Default for LayeredDecimal {
+    fn default() -> Self {
+        LayeredDecimal::ZERO
+    }
+}
+
+impl LayeredDecimal {
+    pub const ZERO: LayeredDecimal = LayeredDecimal {
+        sign: 0,
+        layer: 0,
+        magnitude: ZERO,
+    };
+
+    pub const ONE: LayeredDecimal = LayeredDecimal {
+        sign: 1,
+        layer: 0,
+        magnitude: ONE,
+    };
+
+    pub const NAN: LayeredDecimal = LayeredDecimal {
+        sign: 0,
+        layer: 0,
+        magnitude: NAN,
+    };
+
+    pub const INFINITY: LayeredDecimal = LayeredDecimal {
+        sign: 1,
+        layer: 0,
+        magnitude: INFINITY,
+    };
+
+    pub const NEG_INFINITY: LayeredDecimal = LayeredDecimal {
+        sign: -1,
+        layer: 0,
+        magnitude: INFINITY,
+    };
+
+    /// Creates a new instance of LayeredDecimal with the given value.
+    pub fn new(value: f64) -> LayeredDecimal {
+        if f64::is_infinite(value) {
+            return LayeredDecimal::infinity(value.signum() as i8);
+        }
+        LayeredDecimal::from(Decimal::new(value))
+    }
+
+    /// Creates a new instance of LayeredDecimal with the value `sign * 10^10^...^magnitude`
+    /// (with `layer` tens), normalizing the components.
+    pub fn from_components(sign: i8, layer: u64, magnitude: Decimal) -> LayeredDecimal {
+        if magnitude.is_nan() {
+            return LayeredDecimal::NAN;
+        }
+
+        let mut sign = sign.signum();
+        let mut layer = layer;
+        let mut magnitude = if magnitude.is_zero() { ZERO } else { magnitude };
+        if layer == 0 && magnitude.mantissa < 0.0 {
+            sign = -sign;
+            magnitude = magnitude.abs();
+        }
+
+        loop {
+            if sign == 0 || (layer == 0 && magnitude.mantissa == 0.0) {
+                return LayeredDecimal::ZERO;
+            }
+            if f64::is_infinite(magnitude.exponent) || f64::is_infinite(magnitude.mantissa) {
+                // a negative magnitude on a higher layer is the reciprocal
+                return if magnitude.mantissa < 0.0 {
+                    LayeredDecimal::ZERO
+                } else {
+                    LayeredDecimal::infinity(sign)
+                };
+            }
+
+            let value = magnitude.to_number();
+            if magnitude.exponent >= EXP_LIMIT {
+                let Some(next_layer) = layer.checked_add(1) else {
+                    return if magnitude.mantissa < 0.0 {
+                        LayeredDecimal::ZERO
+                    } else {
+                        LayeredDecimal::infinity(sign)
+                    };
+                };
+                layer = next_layer;
+                magnitude = Decimal::new(magnitude.abs_log10()) * magnitude.sign();
+            } else if layer == 0 || value.abs() >= EXP_LIMIT {
+                return LayeredDecimal {
+                    sign,
+                    layer,
+                    magnitude,
+                };
+            } else if layer == 1 {
+                layer = 0;
+                magnitude = TEN.powf(value);
+            } else {
+                layer -= 1;
+                magnitude = TEN.powf(value.abs()) * magnitude.sign();
+            }
+        }
+    }
+
+    /// Returns -1, 0 or 1 for negative values, zero and positive values (and 0 for NaN).
+    pub fn sign(&self) -> i8 {
+        self.sign
+    }
+
+    /// Returns the number of tens in the power tower.
+    pub fn layer(&self) -> u64 {
+        self.layer
+    }
+
+    /// Returns the magnitude, which is on top of the power tower.
+    pub fn magnitude(&self) -> Decimal {
+        self.magnitude
+    }
+
+    /// Converts the LayeredDecimal into an f64; values beyond its range become infinity or zero.
+    pub fn to_number(&self) -> f64 {
+        if self.layer == 0 {
+            return self.sign as f64 * self.magnitude.to_number();
+        }
+        let value = if self.magnitude.mantissa > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        };
+        self.sign as f64 * value
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.magnitude.is_nan()
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        f64::is_infinite(self.magnitude.exponent)
+    }
+
+    pub fn is_finite(&self) -> bool {
+        !self.is_nan() && !self.is_infinite()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.sign == 0 && !self.is_nan()
+    }
+
+    pub fn abs(&self) -> LayeredDecimal {
+        LayeredDecimal {
+            sign: self.sign.abs(),
+            ..*self
+        }
+    }
+
+    pub fn recip(&self) -> LayeredDecimal {
+        if self.is_nan() || self.is_zero() {
+            LayeredDecimal::NAN
+        } else if self.is_infinite() {
+            LayeredDecimal::ZERO
+        } else if self.layer == 0 {
+            LayeredDecimal::from_components(self.sign, 0, ONE / self.magnitude)
+        } else {
+            LayeredDecimal::from_components(self.sign, self.layer, -self.magnitude)
+        }
+    }
+
+    /// Returns 10^self.
+    pub fn pow10(&self) -> LayeredDecimal {
+        if self.is_nan() {
+            LayeredDecimal::NAN
+        } else if self.is_infinite() {
+            if self.sign > 0 {
+                LayeredDecimal::INFINITY
+            } else {
+                LayeredDecimal::ZERO
+            }
+        } else if self.layer == 0 {
+            LayeredDecimal::from_components(1, 1, self.magnitude * self.sign as f64)
+        } else if self.magnitude.mantissa < 0.0 {
+            // 10^x for |x| < 1e-1.79e308 is 1 within any precision
+            LayeredDecimal::ONE
+        } else {
+            let magnitude = self.magnitude * self.sign as f64;
+            match self.layer.checked_add(1) {
+                Some(layer) => LayeredDecimal::from_components(1, layer, magnitude),
+                None if self.sign > 0 => LayeredDecimal::INFINITY,
+                None => LayeredDecimal::ZERO,
+            }
+        }
+    }
+
+    /// Returns the base 10 logarithm of self; negative values have none (NaN).
+    pub fn log10(&self) -> LayeredDecimal {
+        if self.is_nan() || self.sign < 0 {
+            LayeredDecimal::NAN
+        } else if self.is_zero() {
+            LayeredDecimal::NEG_INFINITY
+        } else if self.is_infinite() {
+            LayeredDecimal::INFINITY
+        } else if self.layer == 0 {
+            LayeredDecimal::from(Decimal::new(self.magnitude.log10()))
+        } else {
+            LayeredDecimal::from_components(
+                self.magnitude.sign() as i8,
+                self.layer - 1,
+                self.magnitude.abs(),
+            )
+        }
+    }
+
+    pub fn log(&self, base: f64) -> LayeredDecimal {
+        self.log10() / LayeredDecimal::new(base.log10())
+    }
+
+    pub fn ln(&self) -> LayeredDecimal {
+        self.log10() * LayeredDecimal::new(LN_10)
+    }
+
+    /// Returns e^self.
+    pub fn exp(&self) -> LayeredDecimal {
+        (self * LayeredDecimal::new(LOG10_E)).pow10()
+    }
+
+    /// Raises self to the power of the given LayeredDecimal; negative values can only be raised
+    /// to integer powers (huge powers, which can't be odd anymore, count as even).
+    pub fn pow(&self, exponent: &LayeredDecimal) -> LayeredDecimal {
+        if self.is_nan() || exponent.is_nan() {
+            return LayeredDecimal::NAN;
+        }
+        if exponent.is_zero() {
+            return LayeredDecimal::ONE;
+        }
+        if self.is_zero() {
+            return if exponent.sign > 0 {
+                LayeredDecimal::ZERO
+            } else {
+                LayeredDecimal::INFINITY
+            };
+        }
+
+        let sign = if self.sign > 0 {
+            1
+        } else if exponent.layer > 0 {
+            // integers of 1e1.79e308 or more are even, smaller exponents are fractions
+            if exponent.magnitude.mantissa > 0.0 {
+                1
+            } else {
+                return LayeredDecimal::NAN;
+            }
+        } else if !exponent.magnitude.is_integer() {
+            return LayeredDecimal::NAN;
+        } else if exponent.magnitude < Decimal::new(MAX_SAFE_INTEGER)
+            && exponent.magnitude.to_number() % 2.0 == 1.0
+        {
+            -1
+        } else {
+            1
+        };
+
+        if self.layer == 0 && exponent.layer == 0 {
+            // Fast track: Decimal's pow, as long as its result agrees with the logarithm
+            let power = exponent.sign as f64 * exponent.magnitude.to_number();
+            let log10 = self.magnitude.log10() * power;
+            if f64::is_finite(log10) && log10.abs() < EXP_LIMIT {
+                let result = self.magnitude.powf(power);
+                if result.is_finite()
+                    && !result.is_zero()
+                    && (result.log10() - log10).abs() <= 1e-9 * log10.abs().max(1.0)
+                {
+                    return LayeredDecimal::from_components(sign, 0, result);
+                }
+            }
+        }
+
+        (self.abs().log10() * exponent).pow10().with_sign(sign)
+    }
+
+    pub fn powf(&self, exponent: f64) -> LayeredDecimal {
+        self.pow(&LayeredDecimal::new(exponent))
+    }
+
+    pub fn sqrt(&self) -> LayeredDecimal {
+        self.powf(0.5)
+    }
+
+    /// Tetration: self^self^...^self, with `height` selfs in the tower.
+    ///
+    /// Fractional heights use the linear approximation (self^^x = x + 1 for -1 < x <= 0);
+    /// heights below -1 have no result (NaN).
+    pub fn tetrate(&self, height: f64) -> LayeredDecimal {
+        if self.is_nan() || f64::is_nan(height) || height < -1.0 {
+            return LayeredDecimal::NAN;
+        }
+        if height < 0.0 {
+            return LayeredDecimal::new(height + 1.0);
+        }
+
+        let whole = height.floor();
+        let fraction = if f64::is_finite(height) {
+            height - whole
+        } else {
+            0.0
+        };
+        let start = if fraction == 0.0 {
+            LayeredDecimal::ONE
+        } else {
+            self.powf(fraction)
+        };
+        self.exponentiate(start, whole)
+    }
+
+    /// The super-logarithm: how often `base` has to be tetrated to reach self,
+    /// the inverse of `tetrate` (including its linear approximation).
+    ///
+    /// Only bases above e^(1/e) have a super-logarithm for every positive value; NaN is returned
+    /// for bases of 1 or less, negative values and where the iteration doesn't get below one.
+    pub fn slog(&self, base: f64) -> f64 {
+        if self.is_nan() || f64::is_nan(base) || base <= 1.0 || self.sign < 0 {
+            return f64::NAN;
+        }
+        if self.is_infinite() {
+            return f64::INFINITY;
+        }
+
+        let mut value = *self;
+        let mut count = 0.0;
+        for _ in 0..MAX_ITERATIONS {
+            if value <= LayeredDecimal::ONE {
+                return count + value.to_number() - 1.0;
+            }
+
+            let next = value.log(base);
+            if value.layer > 2 && next.layer + 1 == value.layer && next.magnitude == value.magnitude
+            {
+                // every further logarithm just removes a layer
+                count += (value.layer - 2) as f64;
+                value.layer = 2;
+            } else {
+                count += 1.0;
+                value = next;
+            }
+        }
+        f64::NAN
+    }
+
+    /// Takes the logarithm with the given base `times` times.
+    pub fn iterated_log(&self, base: f64, times: u64) -> LayeredDecimal {
+        let mut value = *self;
+        let mut remaining = times;
+        while remaining > 0 && value.is_finite() {
+            let next = value.log(base);
+            remaining -= 1;
+            if next.layer > 2 && next.layer + 1 == value.layer && next.magnitude == value.magnitude
+            {
+                // every further logarithm just removes a layer
+                let skipped = remaining.min(next.layer - 2);
+                remaining -= skipped;
+                value = LayeredDecimal {
+                    layer: next.layer - skipped,
+                    ..next
+                };
+            } else {
+                value = next;
+            }
+        }
+        value
+    }
+
+    /// Adds `difference` to the height of the power tower with the given base which results in self,
+    /// i.e. `base^^(self.slog(base) + difference)`; whole differences are exact,
+    /// fractional ones go through `slog` and `tetrate`.
+    pub fn layer_add(&self, difference: f64, base: f64) -> LayeredDecimal {
+        if f64::is_nan(difference) {
+            LayeredDecimal::NAN
+        } else if difference.fract() != 0.0 {
+            LayeredDecimal::new(base).tetrate(self.slog(base) + difference)
+        } else if difference >= 0.0 {
+            LayeredDecimal::new(base).exponentiate(*self, difference)
+        } else {
+            self.iterated_log(base, -difference as u64)
+        }
+    }
+
+    /// The super square root: the value x with x^x = self.
+    ///
+    /// Values below (1/e)^(1/e), about 0.6922, have none (NaN);
+    /// below one, the larger of the two solutions is returned.
+    pub fn ssqrt(&self) -> LayeredDecimal {
+        if self.is_nan() || *self < LayeredDecimal::new(SSQRT_MIN) {
+            return LayeredDecimal::NAN;
+        }
+        if self.is_infinite() {
+            return LayeredDecimal::INFINITY;
+        }
+
+        // x^x = self <=> x = e^W(ln(self)) = ln(self) / W(ln(self))
+        let ln = self.ln();
+        if ln.layer == 0 && ln.magnitude.exponent < 300.0 {
+            return LayeredDecimal::from(Decimal::new(lambert_w0_f64(ln.to_number())).exp());
+        }
+        let ln_ln = ln.ln();
+        if ln_ln.layer == 0 && ln_ln.magnitude.exponent < 300.0 {
+            // w = W(ln(self)) solves w + ln(w) = ln(ln(self)), which still fits into an f64
+            let target = ln_ln.to_number();
+            let w = lambert_w_from_ln(target, target - target.ln());
+            return LayeredDecimal::from(Decimal::new(w).exp());
+        }
+        // W(x) = ln(x) - ln(ln(x)) + o(1), where o(1) is below any precision by now
+        ln / (ln_ln - ln_ln.ln())
+    }
+
+    /// Pentation: self^^self^^...^^self, with `height` selfs in the tower.
+    ///
+    /// Fractional heights use the same linear approximation as `tetrate`.
+    pub fn pentate(&self, height: f64) -> LayeredDecimal {
+        if self.is_nan() || f64::is_nan(height) || height < -1.0 {
+            return LayeredDecimal::NAN;
+        }
+        if height < 0.0 {
+            return LayeredDecimal::new(height + 1.0);
+        }
+
+        let whole = height.floor();
+        let fraction = if f64::is_finite(height) {
+            height - whole
+        } else {
+            0.0
+        };
+        let mut value = if fraction == 0.0 {
+            LayeredDecimal::ONE
+        } else {
+            self.tetrate(fraction)
+        };
+
+        let mut remaining = whole as u64;
+        let mut iterations = 0;
+        while remaining > 0 && iterations < MAX_ITERATIONS && value.is_finite() {
+            let next = self.tetrate(value.to_number());
+            if next == value {
+                break;
+            }
+            value = next;
+            remaining -= 1;
+            iterations += 1;
+        }
+        value
+    }
+
+    /// Applies `value = self^value` the given (whole) number of times.
+    fn exponentiate(&self, start: LayeredDecimal, times: f64) -> LayeredDecimal {
+        let mut value = start;
+        let mut remaining = times;
+        let mut iterations = 0;
+        while remaining >= 1.0 && iterations < MAX_ITERATIONS && value.is_finite() {
+            let next = self.pow(&value);
+            remaining -= 1.0;
+            iterations += 1;
+            if next == value {
+                // converged, like 2^0.5^0.5^...
+                break;
+            }
+            if value.layer > 0 && next.layer == value.layer + 1 && next.magnitude == value.magnitude
+            {
+                // every further exponentiation just adds a layer
+                let layer = (remaining < u64::MAX as f64)
+                    .then(|| next.layer.checked_add(remaining as u64))
+                    .flatten();
+                return match layer {
+                    Some(layer) => LayeredDecimal { layer, ..next },
+                    None => LayeredDecimal::infinity(next.sign),
+                };
+            }
+            value = next;
+        }
+        value
+    }
+
+    // log10(|self|) as a Decimal, only for finite, non-zero values on layers 0 and 1
+    fn abs_log10(&self) -> Decimal {
+        if self.layer == 0 {
+            Decimal::new(self.magnitude.log10())
+        } else {
+            self.magnitude
+        }
+    }
+
+    // zero and NaN keep their sign of 0
+    fn with_sign(self, sign: i8) -> LayeredDecimal {
+        if self.sign == 0 {
+            self
+        } else {
+            LayeredDecimal { sign, ..self }
+        }
+    }
+
+    fn infinity(sign: i8) -> LayeredDecimal {
+        if sign < 0 {
+            LayeredDecimal::NEG_INFINITY
+        } else {
+            LayeredDecimal::INFINITY
+        }
+    }
+
+    /// Compares the absolute values.
+    fn cmp_abs(&self, other: &LayeredDecimal) -> Ordering {
+        self.abs_rank().cmp(&other.abs_rank()).then_with(|| {
+            self.magnitude
+                .partial_cmp(&other.magnitude)
+                .unwrap_or(Equal)
+        })
+    }
+
+    // from smallest to largest absolute value: zero, negative magnitudes on the highest layers
+    // (the smallest reciprocals), ..., layer 0, ..., positive magnitudes on the highest layers, infinity
+    fn abs_rank(&self) -> i128 {
+        if self.is_zero() {
+            i128::MIN
+        } else if self.is_infinite() {
+            i128::MAX
+        } else if self.magnitude.mantissa < 0.0 {
+            -(self.layer as i128)
+        } else {
+            self.layer as i128
+        }
+    }
+}
+
+impl From<Decimal> for LayeredDecimal {
+    /// Only `INFINITY` and `NEG_INFINITY` become infinity; `MAX` and `MIN`, which a Decimal treats
+    /// as infinite, are the finite values 1e1.79e308 and -1e1.79e308 on layer 1 here.
+    fn from(decimal: Decimal) -> LayeredDecimal {
+        LayeredDecimal::from_components(1, 0, decimal)
+    }
+}
+
+impl From<f64> for LayeredDecimal {
+    fn from(value: f64) -> LayeredDecimal {
+        LayeredDecimal::new(value)
+    }
+}
+
+impl TryFrom<LayeredDecimal> for Decimal {
+    type Error = TryFromLayeredError;
+
+    /// Only values on layer 0 (and infinities) fit into a Decimal, along with 1e1.79e308 and
+    /// -1e1.79e308 on layer 1, which `From<Decimal>` makes out of `MAX` and `MIN`.
+    fn try_from(layered: LayeredDecimal) -> Result<Decimal, TryFromLayeredError> {
+        if layered.is_infinite() {
+            Ok(if layered.sign > 0 {
+                INFINITY
+            } else {
+                NEG_INFINITY
+            })
+        } else if layered.layer == 1 && layered.magnitude == Decimal::new(EXP_LIMIT) {
+            Ok(if layered.sign > 0 { MAX } else { MIN })
+        } else if layered.layer == 0 {
+            Ok(layered.magnitude * layered.sign as f64)
+        } else {
+            Err(TryFromLayeredError)
+        }
+    }
+}
+
+impl PartialEq for LayeredDecimal {
+    fn eq(&self, other: &LayeredDecimal) -> bool {
+        self.sign == other.sign && self.layer == other.layer && self.magnitude == other.magnitude
+    }
+}
+
+impl PartialOrd for LayeredDecimal {
+    fn partial_cmp(&self, other: &LayeredDecimal) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            None
+        } else if self.sign != other.sign {
+            Some(self.sign.cmp(&other.sign))
+        } else if self.sign < 0 {
+            Some(other.cmp_abs(self))
+        } else {
+            Some(self.cmp_abs(other))
+        }
+    }
+}
+
+#[opimps::impl_ops(Add)]
+#[inline]
+fn add(self: LayeredDecimal, rhs: LayeredDecimal) -> LayeredDecimal {
+    layered_add(self.to_owned(), rhs.to_owned())
+}
+
+#[opimps::impl_ops_assign(AddAssign)]
+#[inline]
+fn add_assign(self: LayeredDecimal, rhs: LayeredDecimal) {
+    *self = layered_add(self.to_owned(), rhs.to_owned());
+}
+
+#[opimps::impl_ops(Sub)]
+#[inline]
+fn sub(self: LayeredDecimal, rhs: LayeredDecimal) -> LayeredDecimal {
+    layered_add(self.to_owned(), rhs.neg())
+}
+
+#[opimps::impl_ops_assign(SubAssign)]
+#[inline]
+fn sub_assign(self: LayeredDecimal, rhs: LayeredDecimal) {
+    *self = layered_add(self.to_owned(), rhs.neg());
+}
+
+#[opimps::impl_ops(Mul)]
+#[inline]
+fn mul(self: LayeredDecimal, rhs: LayeredDecimal) -> LayeredDecimal {
+    layered_mul(self.to_owned(), rhs.to_owned())
+}
+
+#[opimps::impl_ops_assign(MulAssign)]
+#[inline]
+fn mul_assign(self: LayeredDecimal, rhs: LayeredDecimal) {
+    *self = layered_mul(self.to_owned(), rhs.to_owned());
+}
+
+#[opimps::impl_ops(Div)]
+#[inline]
+fn div(self: LayeredDecimal, rhs: LayeredDecimal) -> LayeredDecimal {
+    layered_mul(self.to_owned(), rhs.recip())
+}
+
+#[opimps::impl_ops_assign(DivAssign)]
+#[inline]
+fn div_assign(self: LayeredDecimal, rhs: LayeredDecimal) {
+    *self = layered_mul(self.to_owned(), rhs.recip());
+}
+
+#[opimps::impl_uni_ops(Neg)]
+#[inline]
+fn neg(self: LayeredDecimal) -> LayeredDecimal {
+    LayeredDecimal {
+        sign: -self.sign,
+        ..self.to_owned()
+    }
+}
+
+fn layered_add(lhs: LayeredDecimal, rhs: LayeredDecimal) -> LayeredDecimal {
+    if lhs.is_nan() || rhs.is_nan() {
+        return LayeredDecimal::NAN;
+    }
+    if lhs.is_infinite() || rhs.is_infinite() {
+        if lhs.is_infinite() && rhs.is_infinite() && lhs.sign != rhs.sign {
+            return LayeredDecimal::NAN;
+        }
+        return if lhs.is_infinite() { lhs } else { rhs };
+    }
+    if lhs.is_zero() {
+        return rhs;
+    }
+    if rhs.is_zero() {
+        return lhs;
+    }
+
+    if lhs.layer == 0 && rhs.layer == 0 {
+        // Fast track: Decimal's add, unless the sum overflows
+        let sum = lhs.magnitude * lhs.sign as f64 + rhs.magnitude * rhs.sign as f64;
+        if !sum.is_infinite() {
+            return LayeredDecimal::from(sum);
+        }
+    }
+
+    let (big, small) = if lhs.cmp_abs(&rhs) == Less {
+        (rhs, lhs)
+    } else {
+        (lhs, rhs)
+    };
+    if big.layer >= 2 {
+        // the smaller value can't change the bigger one anymore, unless they cancel out
+        return if big == -small {
+            LayeredDecimal::ZERO
+        } else {
+            big
+        };
+    }
+
+    // big + small = 10^(log10(big) + log10(1 + small / big))
+    let big_log = big.abs_log10();
+    let difference = (small.abs_log10() - big_log).to_number();
+    if difference < -MAX_SIGNIFICANT_DIGITS_F {
+        return big;
+    }
+    let ratio = 10.0_f64.powf(difference);
+    let factor = if big.sign == small.sign {
+        1.0 + ratio
+    } else {
+        1.0 - ratio
+    };
+    if factor == 0.0 {
+        return LayeredDecimal::ZERO;
+    }
+    LayeredDecimal::from_components(big.sign, 1, big_log + factor.log10())
+}
+
+fn layered_mul(lhs: LayeredDecimal, rhs: LayeredDecimal) -> LayeredDecimal {
+    if lhs.is_nan() || rhs.is_nan() {
+        return LayeredDecimal::NAN;
+    }
+    let sign = lhs.sign * rhs.sign;
+    if lhs.is_zero() || rhs.is_zero() {
+        return if lhs.is_infinite() || rhs.is_infinite() {
+            LayeredDecimal::NAN
+        } else {
+            LayeredDecimal::ZERO
+        };
+    }
+    if lhs.is_infinite() || rhs.is_infinite() {
+        return LayeredDecimal::infinity(sign);
+    }
+
+    if lhs.layer == 0 && rhs.layer == 0 {
+        // Fast track: Decimal's mul, unless the product over- or underflows
+        let product = lhs.magnitude * rhs.magnitude;
+        if product.is_finite() && !product.is_zero() {
+            return LayeredDecimal::from_components(sign, 0, product);
+        }
+    }
+
+    (lhs.abs().log10() + rhs.abs().log10())
+        .pow10()
+        .with_sign(sign)
+}
+
+impl FromStr for LayeredDecimal {
+    type Err = ParseDecimalError;
+
+    /// Parses a LayeredDecimal from anything a Decimal can be parsed from, optionally prefixed
+    /// with one `e` per layer (`ee1e10`) or `(e^N)` for N layers (`(e^5)1e10`),
+    /// or a power tower like `10^^5`.
+    fn from_str(string: &str) -> Result<LayeredDecimal, ParseDecimalError> {
+        if let Some(index) = string.find("^^") {
+            let base: Decimal = string[..index].parse()?;
+            let height: Decimal = string[index + 2..]
+                .parse()
+                .map_err(|error: ParseDecimalError| error.shifted(index + 2))?;
+            return Ok(LayeredDecimal::from(base).tetrate(height.to_number()));
+        }
+
+        let start = string.len() - string.trim_start().len();
+        let trimmed = string.trim();
+        let (negative, unsigned, mut offset) = match trimmed.as_bytes().first() {
+            Some(b'-') => (true, &trimmed[1..], start + 1),
+            Some(b'+') => (false, &trimmed[1..], start + 1),
+            _ => (false, trimmed, start),
+        };
+        if !unsigned.starts_with(['e', 'E', '(']) {
+            let decimal: Decimal = string.parse()?;
+            // `Infinity` is a true infinity here, not the `MAX` it parses to as a Decimal
+            if parse_special(trimmed).is_some() {
+                return Ok(LayeredDecimal::new(decimal.to_number()));
+            }
+            return Ok(LayeredDecimal::from(decimal));
+        }
+
+        let mut rest = unsigned;
+        let mut layer: u64 = 0;
+        if let Some(stripped) = rest.strip_prefix("(e^") {
+            let invalid = ParseDecimalError::InvalidExponent { offset: offset + 3 };
+            let end = stripped.find(')').ok_or(invalid)?;
+            layer = stripped[..end].parse().map_err(|_| invalid)?;
+            rest = &stripped[end + 1..];
+            offset += end + 4;
+        }
+        while let Some(stripped) = rest.strip_prefix(['e', 'E']) {
+            layer = layer
+                .checked_add(1)
+                .ok_or(ParseDecimalError::ExponentOutOfRange { offset })?;
+            rest = stripped;
+            offset += 1;
+        }
+
+        let magnitude: Decimal = rest
+            .parse()
+            .map_err(|error: ParseDecimalError| error.shifted(offset))?;
+        let layered = if magnitude.is_nan() {
+            LayeredDecimal::NAN
+        } else if magnitude.is_infinite() {
+            LayeredDecimal::from(magnitude).pow10()
+        } else {
+            LayeredDecimal::from_components(1, layer, magnitude)
+        };
+        Ok(if negative { -layered } else { layered })
+    }
+}
+
+/// Layer 0 is displayed like a Decimal, higher layers are prefixed with one `e` per layer,
+/// or `(e^N)` for more than five layers; the precision applies to the magnitude.
+impl Display for LayeredDecimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_nan() {
+            return write!(f, "NaN");
+        } else if self.is_infinite() {
+            return if self.sign > 0 {
+                write!(f, "Infinity")
+            } else {
+                write!(f, "-Infinity")
+            };
+        }
+
+        if self.sign < 0 {
+            write!(f, "-")?;
+        }
+        if self.layer > MAX_DISPLAYED_LAYERS {
+            write!(f, "(e^{})", self.layer)?;
+        } else {
+            write!(f, "{}", "e".repeat(self.layer as usize))?;
+        }
+        if let Some(places) = f.precision() {
+            write!(f, "{:.*}", places, self.magnitude)
+        } else {
+            write!(f, "{}", self.magnitude)
+        }
+    }
+}
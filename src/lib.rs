@@ -1,31 +1,77 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("break_infinity_extended needs either the `std` or the `libm` feature for its math");
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::F64Ext;
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+};
 use consts::*;
-use std::{
+use core::{
     cmp::Ordering::{self, *},
     f64::consts::{E, LN_10, LOG2_10, PI},
+    num::FpCategory,
 };
 
 mod calculations;
 mod cmp;
+mod cost;
+mod encoding;
+mod error;
+#[cfg(not(any(feature = "std", test)))]
+mod float;
 mod formatters;
 mod from;
+mod layered;
 mod macros;
 mod ops;
+mod parts;
 mod traits;
 mod utils;
 
 // publicly exported modules and functions
 
 pub mod consts;
-pub use crate::{calculations::*, cmp::*, formatters::*, ops::*, traits::*, utils::*};
+pub mod notation;
+pub mod production;
+// not called `serde`, which would shadow the serde crate wherever this crate is glob-imported
+#[cfg(feature = "serde")]
+pub mod representation;
+pub mod softcap;
+pub use crate::{
+    calculations::*, cmp::*, cost::*, encoding::*, error::*, formatters::*, layered::*, ops::*,
+    traits::*, utils::*,
+};
+
+// called by `dec!`, which hands it the path to this crate
+#[doc(hidden)]
+pub use bie_proc_macros::dec as __dec;
 
 #[cfg(any(feature = "compat", test))]
 mod compat;
 #[cfg(any(feature = "compat", test))]
 pub use crate::compat::*;
 
+#[cfg(feature = "num-traits")]
+mod num;
+
 /// A struct representing a decimal number, which can reach a maximum of 1e1.79e308 instead of `f64`'s maximum of 1.79e308.
+///
+/// Any value with an exponent of `EXP_LIMIT` or more is infinite: `Decimal::new(f64::INFINITY)`
+/// and parsing `"Infinity"` saturate to `MAX` (or `MIN` for the negative counterparts),
+/// which display as `Infinity`, are classified as infinite and convert back to `f64::INFINITY`.
+/// The "true" infinities `INFINITY` and `NEG_INFINITY` behave the same, but compare greater
+/// (or less) than `MAX` and `MIN`. Likewise any value with an exponent of `-EXP_LIMIT` or less is zero.
+///
+/// Results that overflow end up at `MAX` or beyond; results without any limit, like the time to
+/// reach a target that is never reached or the count of free purchases (in the calculations and
+/// `CostModel`), are the true `INFINITY`.
 #[derive(Clone, Copy, Debug)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Decimal {
     mantissa: f64,
     exponent: f64,
@@ -38,7 +84,8 @@ impl Default for Decimal {
 }
 
 impl Decimal {
-    /// Creates a new instance of Decimal with the given value.
+    /// Creates a new instance of Decimal with the given value;
+    /// infinities become `MAX` and `MIN`.
     pub fn new(value: f64) -> Decimal {
         // SAFETY: Handle Infinity and NaN in a somewhat meaningful way.
         if f64::is_nan(value) {
@@ -63,15 +110,144 @@ impl Decimal {
             return MIN;
         }
 
-        let exponent = value.abs().log10().floor();
-        let mantissa = if (exponent - NUMBER_EXP_MIN_F).abs() < f64::EPSILON {
-            value * 10.0 / EXP_MIN_VALUE
-        } else {
-            // This essentially rounds the mantissa for very high numbers.
-            ((value / power_of_10(exponent as i32)) * 1e15).round() / 1e15
-        };
+        let (mantissa, exponent) = parts::new(value);
+        Decimal { mantissa, exponent }
+    }
+
+    /// Creates a new instance of Decimal from the given mantissa and exponent, normalizing them;
+    /// a fractional exponent is folded into the mantissa.
+    ///
+    /// Unlike `from_mantissa_exponent`, invalid input is rejected instead of turning into NaN,
+    /// which makes this suitable for untrusted data such as save files. Like there, values with an
+    /// exponent of `-EXP_LIMIT` or less are flushed to zero; only too large exponents are errors.
+    pub fn try_from_parts(mantissa: f64, exponent: f64) -> Result<Decimal, TryFromPartsError> {
+        if !f64::is_finite(mantissa) {
+            return Err(TryFromPartsError::NonFiniteMantissa);
+        }
+        if !f64::is_finite(exponent) {
+            return Err(TryFromPartsError::NonFiniteExponent);
+        }
+        if mantissa == 0.0 {
+            return Ok(ZERO);
+        }
+
+        let whole = exponent.floor();
+        let decimal = normalize_mantissa_and_exponent(mantissa, whole);
+        let decimal = normalize_mantissa_and_exponent(
+            decimal.mantissa * 10.0_f64.powf(exponent - whole),
+            decimal.exponent,
+        );
+        if decimal.exponent <= NEG_EXP_LIMIT {
+            return Ok(ZERO);
+        }
+        if !f64::is_finite(decimal.exponent) || decimal.exponent > EXP_LIMIT {
+            return Err(TryFromPartsError::ExponentOutOfRange);
+        }
+        Ok(decimal)
+    }
+
+    /// Creates a new instance of Decimal from the given mantissa and exponent, normalizing them
+    /// in const context, where the `log10` of `from_mantissa_exponent` isn't available.
+    ///
+    /// The mantissa is brought into [1, 10) by powers of ten: within 1e22 of that range, the result
+    /// is the exact same as from `from_mantissa_exponent`, further out it may differ in the last bit.
+    /// Like there, a fractional exponent is kept as it is and non-finite parts are NaN.
+    ///
+    /// ```rust
+    /// use break_infinity_extended::Decimal;
+    ///
+    /// // 10 * 1.15^n for the first 50 upgrades
+    /// const UPGRADE_COSTS: [Decimal; 50] = {
+    ///     let mut costs = [Decimal::zero(); 50];
+    ///     let mut cost = 10.0;
+    ///     let mut i = 0;
+    ///     while i < costs.len() {
+    ///         costs[i] = Decimal::from_parts_normalized(cost, 0.0);
+    ///         cost *= 1.15;
+    ///         i += 1;
+    ///     }
+    ///     costs
+    /// };
+    /// assert_eq!(UPGRADE_COSTS[1], Decimal::from_parts_normalized(1.15, 1.0));
+    /// assert!(UPGRADE_COSTS[49].gt(&Decimal::new(9e3)));
+    /// ```
+    pub const fn from_parts_normalized(mantissa: f64, exponent: f64) -> Decimal {
+        if !mantissa.is_finite() || !exponent.is_finite() {
+            return NAN;
+        }
+        if mantissa == 0.0 {
+            return ZERO;
+        }
+
+        // 1e22 is the largest power of ten an f64 holds exactly, so far out it takes several steps
+        let mut mantissa = mantissa;
+        let mut exponent = exponent;
+        while mantissa.abs() >= 1e22 {
+            mantissa /= 1e22;
+            exponent += 22.0;
+        }
+        while mantissa.abs() < 1e-22 {
+            mantissa *= 1e22;
+            exponent -= 22.0;
+        }
+
+        // then a single division by the remaining power of ten, like `normalize_mantissa_and_exponent`
+        let mut power = 1.0;
+        let mut shift = 0.0;
+        if mantissa.abs() >= 10.0 {
+            while mantissa.abs() >= power * 10.0 {
+                power *= 10.0;
+                shift += 1.0;
+            }
+            mantissa /= power;
+            exponent += shift;
+        } else if mantissa.abs() < 1.0 {
+            while mantissa.abs() * power < 1.0 {
+                power *= 10.0;
+                shift += 1.0;
+            }
+            mantissa /= 1.0 / power;
+            exponent -= shift;
+        }
+
+        // a rounded division may end up just outside of the range
+        if mantissa.abs() >= 10.0 {
+            mantissa /= 10.0;
+            exponent += 1.0;
+        } else if mantissa.abs() < 1.0 {
+            mantissa *= 10.0;
+            exponent -= 1.0;
+        }
+        Decimal { mantissa, exponent }
+    }
+
+    /// Returns the mantissa; for normalized Decimals its absolute value is in [1, 10).
+    pub const fn mantissa(&self) -> f64 {
+        self.mantissa
+    }
 
-        normalize_mantissa_and_exponent(mantissa, exponent)
+    /// Returns the (base 10) exponent.
+    pub const fn exponent(&self) -> f64 {
+        self.exponent
+    }
+
+    /// Returns true if the Decimal is in the canonical form all constructors and operations produce:
+    /// NaN, zero, an infinity or a mantissa with an absolute value in [1, 10) and an integer exponent.
+    ///
+    /// `from_bits`, `from_le_bytes` and friends do no validation, so this can be used to detect corrupt values.
+    pub fn is_normalized(&self) -> bool {
+        if f64::is_nan(self.mantissa) || f64::is_nan(self.exponent) {
+            return f64::is_nan(self.mantissa) && f64::is_nan(self.exponent);
+        }
+        if self.mantissa == 0.0 {
+            return self.exponent == 0.0;
+        }
+        if f64::is_infinite(self.exponent) {
+            return self.exponent > 0.0 && self.mantissa.abs() == 1.0;
+        }
+        (1.0..10.0).contains(&self.mantissa.abs())
+            && self.exponent.fract() == 0.0
+            && self.exponent.abs() <= EXP_LIMIT
     }
 
     pub const fn zero() -> Decimal {
@@ -273,10 +449,15 @@ impl Decimal {
     }
 
     /// Returns the absolute value of the Decimal.
-    pub fn abs(&self) -> Decimal {
+    pub const fn abs(&self) -> Decimal {
         from_mantissa_exponent_no_normalize(self.mantissa.abs(), self.exponent)
     }
 
+    /// Returns the negated Decimal, like the `-` operator (which can't be used in const context).
+    pub const fn negate(&self) -> Decimal {
+        from_mantissa_exponent_no_normalize(-self.mantissa, self.exponent)
+    }
+
     #[inline]
     pub fn _sign(&self) -> i32 {
         if self.mantissa.is_sign_positive() {
@@ -362,62 +543,92 @@ impl Decimal {
         self._reciprocal()
     }
 
-    pub fn compare(&self, decimal: &Decimal) -> Option<Ordering> {
-        self.partial_cmp(decimal)
+    /// Returns the ordering between self and the given Decimal, which (unlike `partial_cmp`)
+    /// is total, so it can be used for sorting and in `TotalDecimal`.
+    ///
+    /// NaN is equal to NaN (no matter the bit pattern) and greater than everything else,
+    /// like the positive NaN of `f64::total_cmp`; -0 and 0 are equal though.
+    pub fn total_cmp(&self, decimal: &Decimal) -> Ordering {
+        let is_nan = |d: &Decimal| f64::is_nan(d.mantissa) || f64::is_nan(d.exponent);
+        match (is_nan(self), is_nan(decimal)) {
+            (true, true) => return Equal,
+            (true, false) => return Greater,
+            (false, true) => return Less,
+            (false, false) => {}
+        }
+
+        let sign = |d: &Decimal| d.mantissa.partial_cmp(&0.0).unwrap_or(Equal);
+        match sign(self).cmp(&sign(decimal)) {
+            Equal => {}
+            ordering => return ordering,
+        }
+
+        // + 0.0 turns -0.0 into 0.0
+        let magnitude = (self.exponent + 0.0)
+            .total_cmp(&(decimal.exponent + 0.0))
+            .then_with(|| self.mantissa.abs().total_cmp(&decimal.mantissa.abs()));
+        match sign(self) {
+            Greater => magnitude,
+            Less => magnitude.reverse(),
+            // both are zero, no matter the exponent
+            Equal => Equal,
+        }
     }
 
-    pub fn equals(&self, decimal: &Decimal) -> bool {
-        self.eq(decimal)
+    // the comparisons below are const fns, unlike the operators
+
+    pub const fn equals(&self, decimal: &Decimal) -> bool {
+        self.mantissa == decimal.mantissa && self.exponent == decimal.exponent
     }
 
-    pub fn neq(&self, decimal: &Decimal) -> bool {
-        !self.eq(decimal)
+    pub const fn neq(&self, decimal: &Decimal) -> bool {
+        !self.equals(decimal)
     }
-    pub fn not_equals(&self, decimal: &Decimal) -> bool {
+    pub const fn not_equals(&self, decimal: &Decimal) -> bool {
         !self.neq(decimal)
     }
 
-    pub fn lt(&self, decimal: &Decimal) -> bool {
-        self < decimal
+    pub const fn lt(&self, decimal: &Decimal) -> bool {
+        matches!(self.compare(decimal), Some(Less))
     }
 
-    pub fn lte(&self, decimal: &Decimal) -> bool {
-        self <= decimal
+    pub const fn lte(&self, decimal: &Decimal) -> bool {
+        matches!(self.compare(decimal), Some(Less | Equal))
     }
 
-    pub fn gt(&self, decimal: &Decimal) -> bool {
-        self > decimal
+    pub const fn gt(&self, decimal: &Decimal) -> bool {
+        matches!(self.compare(decimal), Some(Greater))
     }
-    pub fn gte(&self, decimal: &Decimal) -> bool {
-        self >= decimal
+    pub const fn gte(&self, decimal: &Decimal) -> bool {
+        matches!(self.compare(decimal), Some(Greater | Equal))
     }
 
-    pub fn less_than_or_equal_to(&self, other: &Decimal) -> bool {
+    pub const fn less_than_or_equal_to(&self, other: &Decimal) -> bool {
         self.lte(other)
     }
 
-    pub fn less_than(&self, other: &Decimal) -> bool {
+    pub const fn less_than(&self, other: &Decimal) -> bool {
         self.lt(other)
     }
 
-    pub fn greater_than_or_equal_to(&self, other: &Decimal) -> bool {
+    pub const fn greater_than_or_equal_to(&self, other: &Decimal) -> bool {
         self.gte(other)
     }
 
-    pub fn greater_than(&self, other: &Decimal) -> bool {
+    pub const fn greater_than(&self, other: &Decimal) -> bool {
         self.gt(other)
     }
 
-    pub fn max(&self, other: &Decimal) -> Decimal {
-        if self > other {
+    pub const fn max(&self, other: &Decimal) -> Decimal {
+        if self.gt(other) {
             *self
         } else {
             *other
         }
     }
 
-    pub fn min(&self, other: &Decimal) -> Decimal {
-        if self < other {
+    pub const fn min(&self, other: &Decimal) -> Decimal {
+        if self.lt(other) {
             *self
         } else {
             *other
@@ -505,8 +716,11 @@ impl Decimal {
 
     #[inline]
     fn _log(&self, base: f64) -> f64 {
-        // UN-SAFETY: Most incremental game cases are log(number := 1 or greater, base := 2 or greater).
-        // We assume this to be true and thus only need to return a number, not a Decimal,
+        // Most incremental game cases are log(number := 1 or greater, base := 2 or greater),
+        // which always fit into an f64; `log_decimal` covers the rest.
+        if self.mantissa <= 0.0 || f64::is_nan(base) || base <= 1.0 {
+            return f64::NAN;
+        }
         LN_10 / base.ln() * self.log10()
     }
 
@@ -530,6 +744,38 @@ impl Decimal {
         LN_10 * (ONE + self).log10()
     }
 
+    /// log10(self) as a Decimal; NaN for zero and negative values.
+    pub fn log10_decimal(&self) -> Decimal {
+        if self.mantissa <= 0.0 || f64::is_nan(self.mantissa) {
+            return NAN;
+        }
+        Decimal::new(self.log10())
+    }
+
+    /// ln(self) as a Decimal, which unlike `ln` doesn't overflow for values beyond 1e7.8e307
+    /// or below 1e-7.8e307 (such as `ALMOST_ZERO`); NaN for zero and negative values.
+    pub fn ln_decimal(&self) -> Decimal {
+        if self.mantissa <= 0.0 || f64::is_nan(self.mantissa) {
+            return NAN;
+        }
+        let number = self.to_number();
+        if number.is_normal() {
+            return Decimal::new(number.ln());
+        }
+        Decimal::new(self.exponent) * LN_10 + self.mantissa.ln()
+    }
+
+    /// The logarithm of self to a Decimal base.
+    ///
+    /// Unlike `log` it can't overflow, even for bases very close to one;
+    /// a base of one or less, or a value of zero or less, has no result (NaN).
+    pub fn log_decimal(&self, base: &Decimal) -> Decimal {
+        if base.is_nan() || *base <= ONE {
+            return NAN;
+        }
+        self.ln_decimal() / base.ln_decimal()
+    }
+
     #[inline]
     fn _pow(&self, number: f64) -> Decimal {
         if self.mantissa == 0.0 {
@@ -547,10 +793,10 @@ impl Decimal {
         //  fits in a Number, we can do a very fast method.
 
         let temp_exponent = self.exponent * number;
-        if temp_exponent < MAX_SAFE_INTEGER {
+        if temp_exponent.abs() <= MAX_SAFE_INTEGER && temp_exponent.fract() == 0.0 {
             let new_mantissa = self.mantissa.powf(number);
             if f64::is_finite(new_mantissa) && new_mantissa != 0.0 {
-                return from_mantissa_exponent_no_normalize(new_mantissa, temp_exponent);
+                return from_mantissa_exponent(new_mantissa, temp_exponent);
             }
         }
 
@@ -566,7 +812,17 @@ impl Decimal {
             return from_mantissa_exponent(new_mantissa, new_exponent);
         }
 
-        let result = TEN.pow(&Decimal::new(number * self.abs_log10()));
+        // an exponent beyond f64 would come back here through `TEN.pow` over and over
+        let power = number * self.abs_log10();
+        let result = if f64::is_nan(power) {
+            return NAN;
+        } else if power == f64::INFINITY {
+            MAX
+        } else if power == f64::NEG_INFINITY {
+            ZERO
+        } else {
+            TEN.pow(&Decimal::new(power))
+        };
 
         if self.sign() == -1 && (number % 2.0 - 1.0).abs() < f64::EPSILON {
             return result.neg();
@@ -610,13 +866,105 @@ impl Decimal {
         Decimal::from(number)._pow(self.to_number())
     }
 
+    /// self! = Γ(self + 1); see `gamma`.
     pub fn factorial(&self) -> Decimal {
-        //  Using Stirling's Approximation.
-        //  https://en.wikipedia.org/wiki/Stirling%27s_approximation#Versions_suitable_for_calculators
-        let n = self.to_number() + 1.0;
-        Decimal::new(n / E * (n * f64::sinh(1.0 / n) + 1.0 / (810.0 * n.powi(6))))
-            .pow(&Decimal::new(n))
-            * Decimal::new(f64::sqrt(2.0 * PI / n))
+        (self + ONE).gamma()
+    }
+
+    /// The gamma function, Γ(n) = (n - 1)! for positive integers.
+    ///
+    /// Integers up to 171 are looked up from a table of correctly rounded factorials,
+    /// other values use the Lanczos approximation and, once that would overflow an f64, `ln_gamma`.
+    /// Zero and negative integers are poles (NaN).
+    pub fn gamma(&self) -> Decimal {
+        if self.is_nan() || (self.is_integer() && self.mantissa <= 0.0) {
+            return NAN;
+        }
+
+        let number = self.to_number();
+        if self.is_integer() && number <= (MAX_FACTORIAL + 1) as f64 {
+            return Decimal::new(FACTORIALS[number as usize - 1]);
+        }
+        if number < -(MAX_FACTORIAL as f64) {
+            // reflection formula, Γ(1 - self) is beyond f64's range
+            return Decimal::new(PI / (PI * number).sin()) / (ONE - self).gamma();
+        }
+        if number < (MAX_FACTORIAL + 1) as f64 {
+            return Decimal::new(gamma_f64(number));
+        }
+        exp_decimal(&self.ln_gamma())
+    }
+
+    /// The natural logarithm of the absolute value of the gamma function;
+    /// it doesn't overflow for huge arguments (Stirling's series is used from 1e7 on).
+    pub fn ln_gamma(&self) -> Decimal {
+        if self.is_nan() {
+            return NAN;
+        }
+        if self.is_integer() && self.mantissa <= 0.0 {
+            return MAX;
+        }
+
+        let number = self.to_number();
+        if number < 1e7 {
+            return Decimal::new(ln_gamma_f64(number));
+        }
+        //  ln(Γ(x)) = (x - 0.5) * ln(x) - x + ln(2 * PI) / 2 + 1 / (12 * x) - ...
+        let ln = self.ln();
+        self * (ln - 1.0) - (0.5 * ln - LN_SQRT_2PI - 1.0 / (12.0 * number))
+    }
+
+    /// The Lambert W function (principal branch W₀), the inverse of w * e^w:
+    /// the w >= -1 with w * e^w = self. Values below -1/e have none (NaN).
+    ///
+    /// Beyond the range of f64 it solves w + ln(w) = ln(self) instead, and once even ln(self)
+    /// doesn't fit into an f64, uses W(x) = ln(x) - ln(ln(x)) + o(1).
+    pub fn lambert_w(&self) -> Decimal {
+        let number = self.to_number();
+        if self.is_nan() || number < LAMBERT_W_MIN - LAMBERT_W_TOLERANCE {
+            return NAN;
+        }
+        if self.is_infinite() {
+            return MAX;
+        }
+        if number.abs() < 1e-17 {
+            // W(x) = x - x^2 + ...
+            return *self;
+        }
+        if number <= 1e100 {
+            return Decimal::new(lambert_w0_f64(number));
+        }
+
+        let ln = self.ln();
+        if f64::is_finite(ln) {
+            return Decimal::new(lambert_w_from_ln(ln, ln - ln.ln()));
+        }
+        let ln = Decimal::new(self.log10()) * LN_10;
+        ln - ln.ln()
+    }
+
+    /// The lower branch W₋₁ of the Lambert W function: the w <= -1 with w * e^w = self,
+    /// for -1/e <= self < 0 (NaN otherwise). It goes to -infinity as self approaches zero.
+    pub fn lambert_w_m1(&self) -> Decimal {
+        let number = self.to_number();
+        if self.is_nan()
+            || self.is_zero()
+            || self.is_positive()
+            || number < LAMBERT_W_MIN - LAMBERT_W_TOLERANCE
+        {
+            return NAN;
+        }
+        if number < -0.25 {
+            return Decimal::new(lambert_w_m1_f64(number));
+        }
+
+        // w + ln(-w) = ln(-self)
+        let ln = (-self).ln();
+        if f64::is_finite(ln) {
+            return Decimal::new(lambert_w_from_ln(ln, ln - (-ln).ln()));
+        }
+        let ln = Decimal::new((-self).log10()) * LN_10;
+        ln - (-ln).ln()
     }
 
     /// e^self
@@ -737,6 +1085,98 @@ impl Decimal {
         self.mul(a).add(b)
     }
 
+    /// The quotient of the Euclidean division; like `f64::div_euclid`,
+    /// it is rounded so that `self == q * decimal + self.rem_euclid(decimal)`.
+    pub fn div_euclid(&self, decimal: &Decimal) -> Decimal {
+        let quotient = (self / decimal).trunc();
+        if (self % decimal).mantissa < 0.0 {
+            return if decimal.mantissa > 0.0 {
+                quotient - ONE
+            } else {
+                quotient + ONE
+            };
+        }
+        quotient
+    }
+
+    /// The remainder of the Euclidean division, it is never negative;
+    /// see `%` for the precision of the remainder.
+    pub fn rem_euclid(&self, decimal: &Decimal) -> Decimal {
+        let remainder = self % decimal;
+        if remainder.mantissa < 0.0 {
+            return remainder + decimal.abs();
+        }
+        remainder
+    }
+
+    /// The remainder of the floored division, it has the same sign as the divisor
+    /// (`-1 mod 10 == 9`, `1 mod -10 == -9`); see `%` for the precision of the remainder.
+    pub fn modulo(&self, decimal: &Decimal) -> Decimal {
+        let remainder = self % decimal;
+        if remainder.mantissa != 0.0 && remainder.is_sign_negative() != decimal.is_sign_negative() {
+            return remainder + decimal;
+        }
+        remainder
+    }
+
+    /// Returns true if the Decimal is NaN.
+    pub fn is_nan(&self) -> bool {
+        f64::is_nan(self.mantissa) || f64::is_nan(self.exponent)
+    }
+
+    /// Returns true if the Decimal is neither infinite nor NaN.
+    pub fn is_finite(&self) -> bool {
+        !self.is_nan() && !self.is_infinite()
+    }
+
+    /// Returns true if the Decimal is infinite, which includes `MAX` and `MIN`.
+    pub fn is_infinite(&self) -> bool {
+        !self.is_nan() && (self.exponent >= EXP_LIMIT || f64::is_infinite(self.mantissa))
+    }
+
+    /// Returns true if the Decimal is zero, which includes values with an exponent of `-EXP_LIMIT` or less.
+    ///
+    /// With the `num-traits` feature, this takes precedence over `num_traits::Zero::is_zero`
+    /// in method calls; the trait method returns the very same.
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0.0 || self.exponent <= NEG_EXP_LIMIT
+    }
+
+    /// Returns true if the Decimal is neither zero, infinite, NaN nor subnormal.
+    pub fn is_normal(&self) -> bool {
+        self.classify() == FpCategory::Normal
+    }
+
+    /// Returns true if the Decimal has no fractional part;
+    /// this is always the case from an exponent of `MAX_SIGNIFICANT_DIGITS` onwards.
+    pub fn is_integer(&self) -> bool {
+        if self.is_zero() {
+            return true;
+        }
+        if !self.is_finite() || self.exponent < 0.0 {
+            return false;
+        }
+        self.exponent >= MAX_SIGNIFICANT_DIGITS as f64 || self.to_number().fract() == 0.0
+    }
+
+    /// Returns the floating point category of the Decimal.
+    ///
+    /// A finite, non-zero Decimal is `Subnormal` if its mantissa is not normalized,
+    /// meaning its absolute value is not within `1.0..10.0`.
+    pub fn classify(&self) -> FpCategory {
+        if self.is_nan() {
+            FpCategory::Nan
+        } else if self.is_infinite() {
+            FpCategory::Infinite
+        } else if self.is_zero() {
+            FpCategory::Zero
+        } else if (1.0..10.0).contains(&self.mantissa.abs()) {
+            FpCategory::Normal
+        } else {
+            FpCategory::Subnormal
+        }
+    }
+
     pub fn is_sign_positive(&self) -> bool {
         self.mantissa.is_sign_positive()
     }
@@ -760,14 +1200,14 @@ impl Decimal {
     // NOTE: no matter the endianness of the machine,
     // the mantissa always comes first, then the exponent.
 
-    pub fn from_bits(bits: &[u64; 2]) -> Decimal {
+    pub const fn from_bits(bits: &[u64; 2]) -> Decimal {
         Decimal {
             mantissa: f64::from_bits(bits[0]),
             exponent: f64::from_bits(bits[1]),
         }
     }
 
-    pub fn to_bits(&self) -> [u64; 2] {
+    pub const fn to_bits(&self) -> [u64; 2] {
         [self.mantissa.to_bits(), self.exponent.to_bits()]
     }
 
@@ -813,20 +1253,125 @@ impl Decimal {
         self.sinh() / self.cosh()
     }
 
-    pub fn asinh(&self) -> f64 {
-        (self + (self.sqr() + ONE).sqrt()).ln()
+    pub fn asinh(&self) -> Decimal {
+        if self.exponent < -300.0 {
+            // asinh(x) = x - x^3 / 6 + ...
+            return *self;
+        }
+        let number = self.to_number();
+        if number.abs() < 1e300 {
+            return Decimal::new(number.asinh());
+        }
+        // asinh(x) = sign(x) * ln(2 * |x|) + o(1)
+        let ln = (self.abs() * TWO).ln_decimal();
+        if self.is_negative() {
+            -ln
+        } else {
+            ln
+        }
     }
 
-    pub fn acosh(&self) -> f64 {
-        (self + (self.sqr() - ONE).sqrt()).ln()
+    pub fn acosh(&self) -> Decimal {
+        if self.lt(&ONE) {
+            return NAN;
+        }
+        let number = self.to_number();
+        if number < 1e300 {
+            return Decimal::new(number.acosh());
+        }
+        // acosh(x) = ln(2 * x) + o(1)
+        (self * TWO).ln_decimal()
     }
 
-    pub fn atanh(&self) -> f64 {
+    pub fn atanh(&self) -> Decimal {
         if self.abs().gte(&ONE) {
+            return NAN;
+        }
+        if self.exponent < -300.0 {
+            // atanh(x) = x + x^3 / 3 + ...
+            return *self;
+        }
+        Decimal::new(self.to_number().atanh())
+    }
+
+    // Trigonometry
+
+    /// The argument as an f64 for the trigonometric functions, which reduce it modulo 2π exactly.
+    ///
+    /// Beyond that the reduction can't help: a Decimal only carries about 16 significant digits,
+    /// so at 1e6 only about 10 digits of the phase are left, and once |self| exceeds
+    /// `MAX_SAFE_INTEGER` (2^53) neighbouring values are more than a radian apart.
+    /// From there on there is no meaningful phase, and the trigonometric functions return NaN.
+    #[inline]
+    fn _trig_argument(&self) -> f64 {
+        if self.abs().gt(&Decimal::new(MAX_SAFE_INTEGER)) {
             return f64::NAN;
         }
+        self.to_number()
+    }
 
-        ((ONE + self) / (ONE - self)).ln() / 2.0
+    /// The sine of self (in radians); NaN once |self| > 2^53, where there is no meaningful phase.
+    pub fn sin(&self) -> Decimal {
+        if self.exponent < -300.0 {
+            // sin(x) = x - x^3 / 6 + ...
+            return *self;
+        }
+        Decimal::new(self._trig_argument().sin())
+    }
+
+    /// The cosine of self (in radians); NaN once |self| > 2^53, where there is no meaningful phase.
+    pub fn cos(&self) -> Decimal {
+        Decimal::new(self._trig_argument().cos())
+    }
+
+    /// The tangent of self (in radians); NaN once |self| > 2^53, where there is no meaningful phase.
+    pub fn tan(&self) -> Decimal {
+        if self.exponent < -300.0 {
+            // tan(x) = x + x^3 / 3 + ...
+            return *self;
+        }
+        Decimal::new(self._trig_argument().tan())
+    }
+
+    /// The arcsine of self, in [-π/2, π/2]; NaN outside of [-1, 1].
+    pub fn asin(&self) -> Decimal {
+        if self.exponent < -300.0 {
+            // asin(x) = x + x^3 / 6 + ...
+            return *self;
+        }
+        Decimal::new(self.to_number().asin())
+    }
+
+    /// The arccosine of self, in [0, π]; NaN outside of [-1, 1].
+    pub fn acos(&self) -> Decimal {
+        Decimal::new(self.to_number().acos())
+    }
+
+    /// The arctangent of self, in [-π/2, π/2].
+    pub fn atan(&self) -> Decimal {
+        if self.exponent < -300.0 {
+            // atan(x) = x - x^3 / 3 + ...
+            return *self;
+        }
+        Decimal::new(self.to_number().atan())
+    }
+
+    /// The angle from the positive x-axis to the point (x, self), in [-π, π]; see `f64::atan2`.
+    pub fn atan2(&self, x: &Decimal) -> Decimal {
+        if x.mantissa > 0.0 {
+            return (self / x).atan();
+        }
+        // scale both to the larger exponent, so that neither over- nor underflows
+        let exponent = if self.mantissa == 0.0 {
+            x.exponent
+        } else if x.mantissa == 0.0 {
+            self.exponent
+        } else {
+            self.exponent.max(x.exponent)
+        };
+        let scaled =
+            |decimal: &Decimal| decimal.mantissa * 10.0_f64.powf(decimal.exponent - exponent);
+        Decimal::new(scaled(self).atan2(scaled(x)))
     }
 
     #[inline]
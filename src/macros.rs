@@ -8,3 +8,92 @@ macro_rules! impl_from {
         }
     };
 }
+
+/// Implements the arithmetic operators (and their assigning variants) between Decimal and a primitive
+/// number type in both directions; the primitive is converted to f64 and handed to the given functions.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_primitive_ops {
+    ($primitive:ty) => {
+        $crate::impl_primitive_ops!(@op $primitive, Add, add, AddAssign, add_assign, add_f64, f64_add);
+        $crate::impl_primitive_ops!(@op $primitive, Sub, sub, SubAssign, sub_assign, sub_f64, f64_sub);
+        $crate::impl_primitive_ops!(@op $primitive, Mul, mul, MulAssign, mul_assign, mul_f64, f64_mul);
+        $crate::impl_primitive_ops!(@op $primitive, Div, div, DivAssign, div_assign, div_f64, f64_div);
+        $crate::impl_primitive_ops!(@op $primitive, Rem, rem, RemAssign, rem_assign, rem_f64, f64_rem);
+    };
+    (@op $primitive:ty, $op:ident, $method:ident, $op_assign:ident, $method_assign:ident, $decimal_lhs:ident, $decimal_rhs:ident) => {
+        impl $op<$primitive> for Decimal {
+            type Output = Decimal;
+
+            #[inline]
+            fn $method(self, rhs: $primitive) -> Decimal {
+                $decimal_lhs(&self, rhs as f64)
+            }
+        }
+
+        impl $op<$primitive> for &Decimal {
+            type Output = Decimal;
+
+            #[inline]
+            fn $method(self, rhs: $primitive) -> Decimal {
+                $decimal_lhs(self, rhs as f64)
+            }
+        }
+
+        impl $op<Decimal> for $primitive {
+            type Output = Decimal;
+
+            #[inline]
+            fn $method(self, rhs: Decimal) -> Decimal {
+                $decimal_rhs(self as f64, &rhs)
+            }
+        }
+
+        impl $op<&Decimal> for $primitive {
+            type Output = Decimal;
+
+            #[inline]
+            fn $method(self, rhs: &Decimal) -> Decimal {
+                $decimal_rhs(self as f64, rhs)
+            }
+        }
+
+        impl $op_assign<$primitive> for Decimal {
+            #[inline]
+            fn $method_assign(&mut self, rhs: $primitive) {
+                *self = $decimal_lhs(self, rhs as f64);
+            }
+        }
+    };
+}
+
+/// A Decimal from a number or string literal, parsed and normalized at compile time,
+/// so it can be used for constants: `dec!(1.5e1000)`, `dec!(-0.25)` or `dec!("1e1e5")`.
+///
+/// With the `std` feature, the value is exactly the one `str::parse` gives for the same text.
+/// Without it, `str::parse` does its math with `libm`, while literals are still parsed
+/// with the compiler's own, so the two may differ in the last bit. Malformed literals
+/// or exponents beyond `EXP_LIMIT` are compile errors.
+///
+/// ```rust
+/// use break_infinity_extended::{dec, from_mantissa_exponent, Decimal};
+///
+/// const BASE_COST: Decimal = dec!(1.5e1000);
+/// const SCALING: Decimal = dec!(1_000);
+/// const SHRINK: Decimal = dec!(-2.5e-3);
+///
+/// assert_eq!(BASE_COST, from_mantissa_exponent(1.5, 1000.0));
+/// assert_eq!(SCALING, Decimal::new(1000.0));
+/// assert_eq!(SHRINK, "-2.5e-3".parse().unwrap());
+/// ```
+///
+/// ```compile_fail
+/// # use break_infinity_extended::{dec, Decimal};
+/// const BROKEN: Decimal = dec!("1.5e");
+/// ```
+#[macro_export]
+macro_rules! dec {
+    ($($literal:tt)+) => {
+        $crate::__dec!($crate; $($literal)+)
+    };
+}
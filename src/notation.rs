@@ -0,0 +1,229 @@
+//! Configurable notations for displaying Decimals, modelled on the notations of Antimatter Dimensions
+//! (<https://github.com/antimatter-dimensions/notations>).
+//!
+//! ```rust
+//! use break_infinity_extended::{notation::*, Decimal};
+//!
+//! let value = Decimal::new(1.2345e15);
+//! assert_eq!(ScientificNotation.format(&value, 2), "1.23e15");
+//! assert_eq!(EngineeringNotation.format(&value, 2), "1.23e15");
+//! assert_eq!(StandardNotation.format(&value, 2), "1.23 Qa");
+//! assert_eq!(LettersNotation.format(&value, 2), "1.23e");
+//! assert_eq!(LogarithmNotation.format(&value, 2), "e15.09");
+//! ```
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::F64Ext;
+use crate::{consts::*, formatters::short_scale_term, utils::to_fixed, Decimal};
+use alloc::{format, string::String, vec::Vec};
+
+/// Exponents from this value onwards are themselves written in scientific notation.
+const EXPONENT_LIMIT: f64 = 1e9;
+
+/// A way to turn a Decimal into a human readable string.
+///
+/// Only `format_decimal` needs to be implemented; NaN, infinities, negative values
+/// and values below 1000 are handled by `format` before it gets called.
+pub trait Notation {
+    /// The name of the notation, e.g. for a settings menu.
+    fn name(&self) -> &'static str;
+
+    /// Formats a positive, finite value of at least 1000.
+    fn format_decimal(&self, value: &Decimal, places: usize) -> String;
+
+    /// Formats a non-negative value below 1000.
+    fn format_under_1000(&self, value: &Decimal, places: usize) -> String {
+        to_fixed(value.to_number(), places as u32)
+    }
+
+    /// Formats the value with the given number of decimal places.
+    fn format(&self, value: &Decimal, places: usize) -> String {
+        if f64::is_nan(value.mantissa) || f64::is_nan(value.exponent) {
+            String::from("NaN")
+        } else if value.exponent >= EXP_LIMIT {
+            if value.mantissa > 0.0 {
+                String::from("Infinity")
+            } else {
+                String::from("-Infinity")
+            }
+        } else if value.mantissa < 0.0 {
+            String::from("-") + &self.format(&value.abs(), places)
+        } else if value.exponent < 3.0 {
+            self.format_under_1000(value, places)
+        } else {
+            self.format_decimal(value, places)
+        }
+    }
+}
+
+/// `1.23e456`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ScientificNotation;
+
+impl Notation for ScientificNotation {
+    fn name(&self) -> &'static str {
+        "Scientific"
+    }
+
+    fn format_decimal(&self, value: &Decimal, places: usize) -> String {
+        let (mantissa, exponent) = split_rounded(value, places, 1.0);
+        format!("{mantissa:.places$}e{}", format_exponent(exponent, places))
+    }
+}
+
+/// `12.35e3`; like scientific, but the exponent is always a multiple of 3
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EngineeringNotation;
+
+impl Notation for EngineeringNotation {
+    fn name(&self) -> &'static str {
+        "Engineering"
+    }
+
+    fn format_decimal(&self, value: &Decimal, places: usize) -> String {
+        let (mantissa, exponent) = split_rounded(value, places, 3.0);
+        format!("{mantissa:.places$}e{}", format_exponent(exponent, places))
+    }
+}
+
+/// `1.23 Qa`; the short scale terms also used by the `{:b}` formatter,
+/// falls back to scientific notation where no term exists
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StandardNotation;
+
+impl Notation for StandardNotation {
+    fn name(&self) -> &'static str {
+        "Standard"
+    }
+
+    fn format_decimal(&self, value: &Decimal, places: usize) -> String {
+        let (mantissa, exponent) = split_rounded(value, places, 3.0);
+        match short_scale_term((exponent / 3.0) as i64) {
+            Some(term) => format!("{mantissa:.places$} {term}"),
+            None => ScientificNotation.format_decimal(value, places),
+        }
+    }
+}
+
+/// `1.23a`, `1.23b`, ..., `1.23z`, `1.23aa`; every letter is another factor of 1000,
+/// falls back to scientific notation once the exponent can no longer be counted exactly
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LettersNotation;
+
+impl Notation for LettersNotation {
+    fn name(&self) -> &'static str {
+        "Letters"
+    }
+
+    fn format_decimal(&self, value: &Decimal, places: usize) -> String {
+        let (mantissa, exponent) = split_rounded(value, places, 3.0);
+        if exponent >= MAX_SAFE_INTEGER {
+            return ScientificNotation.format_decimal(value, places);
+        }
+
+        // bijective base 26: 1 => a, 26 => z, 27 => aa
+        let mut scale = (exponent / 3.0) as u64;
+        let mut letters = Vec::new();
+        while scale > 0 {
+            scale -= 1;
+            letters.push(b'a' + (scale % 26) as u8);
+            scale /= 26;
+        }
+        letters.reverse();
+
+        format!(
+            "{mantissa:.places$}{}",
+            String::from_utf8(letters).unwrap_or_default()
+        )
+    }
+}
+
+/// `e123.45`; the base 10 logarithm of the value
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LogarithmNotation;
+
+impl Notation for LogarithmNotation {
+    fn name(&self) -> &'static str {
+        "Logarithm"
+    }
+
+    fn format_decimal(&self, value: &Decimal, places: usize) -> String {
+        let log10 = value.log10();
+        if log10 < EXPONENT_LIMIT {
+            format!("e{log10:.places$}")
+        } else {
+            format!(
+                "e{}",
+                ScientificNotation.format(&Decimal::new(log10), places)
+            )
+        }
+    }
+}
+
+/// Standard notation below the threshold (1e33 by default), scientific notation from there on
+#[derive(Clone, Copy, Debug)]
+pub struct MixedScientificNotation {
+    pub threshold: Decimal,
+}
+
+impl Default for MixedScientificNotation {
+    fn default() -> Self {
+        MixedScientificNotation {
+            threshold: Decimal::new(1e33),
+        }
+    }
+}
+
+impl Notation for MixedScientificNotation {
+    fn name(&self) -> &'static str {
+        "Mixed scientific"
+    }
+
+    fn format_decimal(&self, value: &Decimal, places: usize) -> String {
+        if value < &self.threshold {
+            StandardNotation.format_decimal(value, places)
+        } else {
+            ScientificNotation.format_decimal(value, places)
+        }
+    }
+}
+
+/// `1.2345∞`; the value as a multiple of "Infinity" (`f64::MAX`) on a logarithmic scale
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InfinityNotation;
+
+impl Notation for InfinityNotation {
+    fn name(&self) -> &'static str {
+        "Infinity"
+    }
+
+    fn format_decimal(&self, value: &Decimal, places: usize) -> String {
+        let infinities = value.log10() / f64::MAX.log10();
+        format!("{infinities:.places$}∞")
+    }
+}
+
+/// Splits a value into a mantissa rounded to the given places and an exponent,
+/// which is a multiple of `step`; rounding up to the next step (9.999 => 10.00) is handled.
+fn split_rounded(value: &Decimal, places: usize, step: f64) -> (f64, f64) {
+    let remainder = value.exponent.rem_euclid(step);
+    let mut exponent = value.exponent - remainder;
+    let factor = 10.0_f64.powi(places as i32);
+    let mut mantissa = (value.mantissa * 10.0_f64.powf(remainder) * factor).round() / factor;
+
+    let limit = 10.0_f64.powf(step);
+    if mantissa >= limit {
+        mantissa /= limit;
+        exponent += step;
+    }
+
+    (mantissa, exponent)
+}
+
+fn format_exponent(exponent: f64, places: usize) -> String {
+    if exponent < EXPONENT_LIMIT {
+        format!("{exponent}")
+    } else {
+        ScientificNotation.format(&Decimal::new(exponent), places)
+    }
+}
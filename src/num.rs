@@ -0,0 +1,196 @@
+//! Implementations of the `num-traits` traits, so Decimal can be used in generic numeric code.
+//!
+//! Most mappings are exact, the lossy ones are:
+//!
+//! - `FromPrimitive`: integers beyond 2^53 (9007199254740992) lose precision, like in `f64`
+//! - `ToPrimitive`: integer conversions truncate toward zero and return `None` for NaN and
+//!   values out of the target range; `to_f64` saturates to ±infinity above `f64::MAX`
+//!   and becomes zero below the smallest subnormal `f64`
+//! - `Signed::signum`: like `f64`, zero has a positive sign (signum of zero is one)
+//! - `Bounded`: the bounds are the largest finite values `MAX_FINITE` and `MIN_FINITE`,
+//!   since `MAX` and `MIN` count as infinite
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::F64Ext;
+use crate::{consts::*, error::ParseDecimalError, Decimal};
+use num_traits::{
+    Bounded, FromPrimitive, Inv, MulAdd, MulAddAssign, Num, One, Pow, Signed, ToPrimitive, Zero,
+};
+
+impl Zero for Decimal {
+    fn zero() -> Decimal {
+        ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        Decimal::is_zero(self)
+    }
+}
+
+impl One for Decimal {
+    fn one() -> Decimal {
+        ONE
+    }
+}
+
+impl Num for Decimal {
+    type FromStrRadixErr = ParseDecimalError;
+
+    /// Only radix 10 is supported.
+    fn from_str_radix(string: &str, radix: u32) -> Result<Decimal, ParseDecimalError> {
+        if radix != 10 {
+            return Err(ParseDecimalError::UnsupportedRadix { radix });
+        }
+        string.parse()
+    }
+}
+
+impl Signed for Decimal {
+    fn abs(&self) -> Decimal {
+        Decimal::abs(self)
+    }
+
+    fn abs_sub(&self, other: &Decimal) -> Decimal {
+        if self <= other {
+            ZERO
+        } else {
+            self - other
+        }
+    }
+
+    fn signum(&self) -> Decimal {
+        if f64::is_nan(self.mantissa) {
+            return NAN;
+        }
+        Decimal::from(self.sign())
+    }
+
+    fn is_positive(&self) -> bool {
+        Decimal::is_positive(self)
+    }
+
+    fn is_negative(&self) -> bool {
+        Decimal::is_negative(self)
+    }
+}
+
+impl Bounded for Decimal {
+    fn min_value() -> Decimal {
+        MIN_FINITE
+    }
+
+    fn max_value() -> Decimal {
+        MAX_FINITE
+    }
+}
+
+impl FromPrimitive for Decimal {
+    fn from_i64(n: i64) -> Option<Decimal> {
+        Some(Decimal::from(n))
+    }
+
+    fn from_u64(n: u64) -> Option<Decimal> {
+        Some(Decimal::from(n))
+    }
+
+    fn from_i128(n: i128) -> Option<Decimal> {
+        Some(Decimal::from(n))
+    }
+
+    fn from_u128(n: u128) -> Option<Decimal> {
+        Some(Decimal::from(n))
+    }
+
+    fn from_f64(n: f64) -> Option<Decimal> {
+        Some(Decimal::new(n))
+    }
+}
+
+impl ToPrimitive for Decimal {
+    fn to_i64(&self) -> Option<i64> {
+        let number = self.to_number().trunc();
+        // i64::MAX as f64 rounds up to 2^63, which is already out of range
+        (number >= i64::MIN as f64 && number < i64::MAX as f64).then_some(number as i64)
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        let number = self.to_number().trunc();
+        (number >= 0.0 && number < u64::MAX as f64).then_some(number as u64)
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        let number = self.to_number().trunc();
+        (number >= i128::MIN as f64 && number < i128::MAX as f64).then_some(number as i128)
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        let number = self.to_number().trunc();
+        (number >= 0.0 && number < u128::MAX as f64).then_some(number as u128)
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.to_number())
+    }
+}
+
+impl Pow<Decimal> for Decimal {
+    type Output = Decimal;
+
+    fn pow(self, rhs: Decimal) -> Decimal {
+        Decimal::pow(&self, &rhs)
+    }
+}
+
+impl Pow<&Decimal> for &Decimal {
+    type Output = Decimal;
+
+    fn pow(self, rhs: &Decimal) -> Decimal {
+        Decimal::pow(self, rhs)
+    }
+}
+
+impl Pow<f64> for Decimal {
+    type Output = Decimal;
+
+    fn pow(self, rhs: f64) -> Decimal {
+        self.powf(rhs)
+    }
+}
+
+impl Pow<i32> for Decimal {
+    type Output = Decimal;
+
+    fn pow(self, rhs: i32) -> Decimal {
+        self.powi(rhs)
+    }
+}
+
+impl Inv for Decimal {
+    type Output = Decimal;
+
+    fn inv(self) -> Decimal {
+        self.recip()
+    }
+}
+
+impl Inv for &Decimal {
+    type Output = Decimal;
+
+    fn inv(self) -> Decimal {
+        self.recip()
+    }
+}
+
+impl MulAdd for Decimal {
+    type Output = Decimal;
+
+    fn mul_add(self, a: Decimal, b: Decimal) -> Decimal {
+        Decimal::mul_add(&self, &a, &b)
+    }
+}
+
+impl MulAddAssign for Decimal {
+    fn mul_add_assign(&mut self, a: Decimal, b: Decimal) {
+        *self = Decimal::mul_add(self, &a, &b);
+    }
+}
@@ -1,9 +1,13 @@
 use crate::{
-    consts::MAX_SIGNIFICANT_DIGITS_F, from_mantissa_exponent, from_mantissa_exponent_no_normalize,
-    power_of_10, Decimal,
+    consts::{MAX_SIGNIFICANT_DIGITS_F, NAN, NUMBER_EXP_MAX, NUMBER_EXP_MIN, ZERO},
+    from_mantissa_exponent, from_mantissa_exponent_no_normalize, impl_primitive_ops,
+    normalize_mantissa_and_exponent, power_of_10, Decimal,
 };
+use alloc::borrow::ToOwned;
 
-pub use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+pub use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
 
 #[opimps::impl_ops(Add)]
 #[inline]
@@ -85,8 +89,125 @@ fn div_assign(self: Decimal, rhs: Decimal) {
     *self = from_mantissa_exponent(self.mantissa / rhs.mantissa, self.exponent - rhs.exponent);
 }
 
+/// The remainder of a truncated division, it has the same sign as self (like `%` for f64).
+///
+/// The result is exact (as exact as f64's `%`) while both values fit into an f64;
+/// beyond that it is calculated from the mantissas. Once rhs is also `MAX_SIGNIFICANT_DIGITS`
+/// or more orders of magnitude smaller than self, self cannot have a remainder in units
+/// of rhs within its precision anymore, so the result is zero.
+///
+/// Like for f64, an infinite self (which includes `MAX` and `MIN`) or a zero rhs gives NaN.
+#[opimps::impl_ops(Rem)]
+#[inline]
+fn rem(self: Decimal, rhs: Decimal) -> Decimal {
+    truncated_remainder(self.to_owned(), rhs.to_owned())
+}
+
+#[opimps::impl_ops_assign(RemAssign)]
+#[inline]
+fn rem_assign(self: Decimal, rhs: Decimal) {
+    *self = self.rem(rhs);
+}
+
+#[inline]
+fn truncated_remainder(lhs: Decimal, rhs: Decimal) -> Decimal {
+    if f64::is_nan(lhs.mantissa)
+        || f64::is_nan(rhs.mantissa)
+        || lhs.is_infinite()
+        || rhs.mantissa == 0.0
+    {
+        return NAN;
+    }
+    if lhs.abs() < rhs.abs() {
+        return lhs;
+    }
+
+    if lhs.exponent < NUMBER_EXP_MAX as f64 && rhs.exponent > NUMBER_EXP_MIN as f64 {
+        return Decimal::new(lhs.to_number() % rhs.to_number());
+    }
+    let difference = lhs.exponent - rhs.exponent;
+    if difference >= MAX_SIGNIFICANT_DIGITS_F {
+        return ZERO;
+    }
+
+    from_mantissa_exponent(
+        (lhs.mantissa * power_of_10(difference as i32)) % rhs.mantissa,
+        rhs.exponent,
+    )
+}
+
 #[opimps::impl_uni_ops(Neg)]
 #[inline]
 fn neg(self: Decimal) -> Decimal {
     from_mantissa_exponent_no_normalize(-self.mantissa, self.exponent)
 }
+
+// Operators between Decimal and primitive numbers.
+//
+// Integers are converted to f64 first, so (like `Decimal::from`) they lose precision beyond 2^53.
+
+impl_primitive_ops!(f64);
+impl_primitive_ops!(i32);
+impl_primitive_ops!(u32);
+impl_primitive_ops!(i64);
+impl_primitive_ops!(u64);
+
+#[inline]
+fn add_f64(lhs: &Decimal, rhs: f64) -> Decimal {
+    lhs.add(Decimal::new(rhs))
+}
+
+#[inline]
+fn f64_add(lhs: f64, rhs: &Decimal) -> Decimal {
+    Decimal::new(lhs).add(rhs)
+}
+
+#[inline]
+fn sub_f64(lhs: &Decimal, rhs: f64) -> Decimal {
+    lhs.sub(Decimal::new(rhs))
+}
+
+#[inline]
+fn f64_sub(lhs: f64, rhs: &Decimal) -> Decimal {
+    Decimal::new(lhs).sub(rhs)
+}
+
+#[inline]
+fn mul_f64(lhs: &Decimal, rhs: f64) -> Decimal {
+    // Fast track: only the mantissa needs to be touched, as long as the product is a normal f64.
+    let mantissa = lhs.mantissa * rhs;
+    if f64::is_normal(mantissa) && f64::is_finite(lhs.exponent) {
+        return normalize_mantissa_and_exponent(mantissa, lhs.exponent);
+    }
+    lhs.mul(Decimal::new(rhs))
+}
+
+#[inline]
+fn f64_mul(lhs: f64, rhs: &Decimal) -> Decimal {
+    mul_f64(rhs, lhs)
+}
+
+#[inline]
+fn div_f64(lhs: &Decimal, rhs: f64) -> Decimal {
+    // Fast track: see mul_f64
+    let mantissa = lhs.mantissa / rhs;
+    if f64::is_normal(mantissa) && f64::is_finite(lhs.exponent) {
+        return normalize_mantissa_and_exponent(mantissa, lhs.exponent);
+    }
+    lhs.div(Decimal::new(rhs))
+}
+
+#[inline]
+fn f64_div(lhs: f64, rhs: &Decimal) -> Decimal {
+    Decimal::new(lhs).div(rhs)
+}
+
+#[inline]
+fn rem_f64(lhs: &Decimal, rhs: f64) -> Decimal {
+    lhs.rem(Decimal::new(rhs))
+}
+
+#[inline]
+fn f64_rem(lhs: f64, rhs: &Decimal) -> Decimal {
+    Decimal::new(lhs).rem(rhs)
+}
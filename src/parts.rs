@@ -0,0 +1,244 @@
+//! Parsing and normalization on plain `(mantissa, exponent)` pairs, shared with the `dec!` macro.
+//!
+//! bie-proc-macros compiles this very file through `#[path]`, so `dec!(x)` is the same value as
+//! `"x".parse::<Decimal>()`; the macro always does its math with the compiler's `std` though,
+//! which may differ in the last bit from `libm`. Both crates provide `EXP_LIMIT`, `power_of_10`
+//! and the constants of `insert_consts_and_cache_for_powers_of_ten` (and without std, `F64Ext`)
+//! in the parent module, so the code only needs core and alloc.
+
+use super::*;
+use alloc::format;
+use core::fmt::{self, Display, Formatter};
+
+/// A mantissa and an exponent; NaN is `(NaN, NaN)`.
+pub(crate) type Parts = (f64, f64);
+
+const NAN: Parts = (f64::NAN, f64::NAN);
+const ZERO: Parts = (0.0, 0.0);
+
+/// An error which can be returned when parsing a Decimal from a string.
+///
+/// All offsets are byte offsets into the original (untrimmed) input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseDecimalError {
+    /// The input was empty or consisted only of whitespace.
+    Empty,
+    /// The mantissa (the part before `e`/`E`) is not a valid number.
+    InvalidMantissa { offset: usize },
+    /// The exponent (the part after `e`/`E`) is not a valid number.
+    InvalidExponent { offset: usize },
+    /// The exponent is valid, but exceeds the range a Decimal can represent.
+    ExponentOutOfRange { offset: usize },
+    /// A valid number was followed by unexpected characters.
+    TrailingCharacters { offset: usize },
+    /// Decimals can only be parsed from base 10 strings.
+    UnsupportedRadix { radix: u32 },
+}
+
+impl Display for ParseDecimalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseDecimalError::Empty => write!(f, "cannot parse decimal from empty string"),
+            ParseDecimalError::InvalidMantissa { offset } => {
+                write!(f, "invalid mantissa at byte {offset}")
+            }
+            ParseDecimalError::InvalidExponent { offset } => {
+                write!(f, "invalid exponent at byte {offset}")
+            }
+            ParseDecimalError::ExponentOutOfRange { offset } => {
+                write!(f, "exponent out of range at byte {offset}")
+            }
+            ParseDecimalError::TrailingCharacters { offset } => {
+                write!(f, "unexpected trailing characters at byte {offset}")
+            }
+            ParseDecimalError::UnsupportedRadix { radix } => {
+                write!(f, "unsupported radix {radix}, only radix 10 is supported")
+            }
+        }
+    }
+}
+
+/// Parses strings like `123.45`, `-1.5e1000`, `1E+5`, `NaN` or `Infinity` into normalized parts.
+pub(crate) fn parse(string: &str) -> Result<Parts, ParseDecimalError> {
+    let start = string.len() - string.trim_start().len();
+    let trimmed = string.trim();
+    if trimmed.is_empty() {
+        return Err(ParseDecimalError::Empty);
+    }
+    if let Some(special) = parse_special(trimmed) {
+        return Ok(special);
+    }
+
+    let bytes = trimmed.as_bytes();
+    let mantissa_end =
+        scan_decimal(bytes, 0).ok_or(ParseDecimalError::InvalidMantissa { offset: start })?;
+    let (mantissa, shift) = parse_mantissa(&trimmed[..mantissa_end]);
+
+    if mantissa_end == bytes.len() {
+        return Ok(if shift == 0.0 {
+            new(mantissa)
+        } else {
+            from_mantissa_exponent(mantissa, shift)
+        });
+    }
+    if !matches!(bytes[mantissa_end], b'e' | b'E') {
+        return Err(ParseDecimalError::TrailingCharacters {
+            offset: start + mantissa_end,
+        });
+    }
+
+    let exponent_start = mantissa_end + 1;
+    let invalid_exponent = ParseDecimalError::InvalidExponent {
+        offset: start + exponent_start,
+    };
+    let mut exponent_end = scan_decimal(bytes, exponent_start).ok_or(invalid_exponent)?;
+    // the exponent itself may be written in scientific notation, like JavaScript's parseFloat allows
+    if let Some(b'e' | b'E') = bytes.get(exponent_end) {
+        exponent_end = scan_integer(bytes, exponent_end + 1).ok_or(invalid_exponent)?;
+    }
+    if exponent_end != bytes.len() {
+        return Err(ParseDecimalError::TrailingCharacters {
+            offset: start + exponent_end,
+        });
+    }
+
+    let exponent: f64 = trimmed[exponent_start..exponent_end]
+        .parse()
+        .map_err(|_| invalid_exponent)?;
+    if !f64::is_finite(exponent) || exponent.abs() > EXP_LIMIT {
+        return Err(ParseDecimalError::ExponentOutOfRange {
+            offset: start + exponent_start,
+        });
+    }
+    if mantissa == 0.0 {
+        return Ok(ZERO);
+    }
+
+    // fractional exponents are folded into the mantissa
+    let integral = exponent.trunc();
+    Ok(from_mantissa_exponent(
+        mantissa * 10.0_f64.powf(exponent - integral),
+        integral + shift,
+    ))
+}
+
+/// Parses `NaN`, `Infinity` and `inf` (case-insensitive, optionally signed);
+/// the infinities are the parts of `MAX` and `MIN`.
+pub(crate) fn parse_special(string: &str) -> Option<Parts> {
+    let (negative, unsigned) = match string.as_bytes()[0] {
+        b'-' => (true, &string[1..]),
+        b'+' => (false, &string[1..]),
+        _ => (false, string),
+    };
+
+    if unsigned.eq_ignore_ascii_case("nan") {
+        Some(NAN)
+    } else if unsigned.eq_ignore_ascii_case("infinity") || unsigned.eq_ignore_ascii_case("inf") {
+        Some((if negative { -1.0 } else { 1.0 }, EXP_LIMIT))
+    } else {
+        None
+    }
+}
+
+/// Parses the mantissa, shifting it down if it does not fit into an `f64`;
+/// returns the mantissa and the amount it was shifted by.
+fn parse_mantissa(string: &str) -> (f64, f64) {
+    let mantissa: f64 = string.parse().expect("scan_decimal validated the mantissa");
+    if f64::is_finite(mantissa) {
+        return (mantissa, 0.0);
+    }
+
+    let digits = string
+        .bytes()
+        .take_while(|b| *b != b'.')
+        .filter(u8::is_ascii_digit)
+        .count();
+    let shifted = format!("{string}e-{digits}")
+        .parse()
+        .expect("scan_decimal validated the mantissa");
+    (shifted, digits as f64)
+}
+
+/// Scans an optionally signed decimal number like `-12.5`, `.5` or `5.`;
+/// returns the end of the number, if there is one at the given position.
+fn scan_decimal(bytes: &[u8], mut pos: usize) -> Option<usize> {
+    if let Some(b'+' | b'-') = bytes.get(pos) {
+        pos += 1;
+    }
+    let integral_digits = count_digits(bytes, pos);
+    pos += integral_digits;
+
+    let mut fractional_digits = 0;
+    if let Some(b'.') = bytes.get(pos) {
+        fractional_digits = count_digits(bytes, pos + 1);
+        pos += 1 + fractional_digits;
+    }
+
+    (integral_digits + fractional_digits > 0).then_some(pos)
+}
+
+/// Scans an optionally signed integer; returns the end of the number, if there is one.
+fn scan_integer(bytes: &[u8], mut pos: usize) -> Option<usize> {
+    if let Some(b'+' | b'-') = bytes.get(pos) {
+        pos += 1;
+    }
+    let digits = count_digits(bytes, pos);
+    (digits > 0).then_some(pos + digits)
+}
+
+fn count_digits(bytes: &[u8], pos: usize) -> usize {
+    bytes
+        .iter()
+        .skip(pos)
+        .take_while(|b| b.is_ascii_digit())
+        .count()
+}
+
+/// The parts of `Decimal::new(value)`.
+pub(crate) fn new(value: f64) -> Parts {
+    if f64::is_nan(value) {
+        return NAN;
+    }
+    if value == 0.0 {
+        return ZERO;
+    }
+    if f64::is_infinite(value) {
+        return (value.signum(), EXP_LIMIT);
+    }
+
+    let exponent = value.abs().log10().floor();
+    let mantissa = if (exponent - NUMBER_EXP_MIN_F).abs() < f64::EPSILON {
+        value * 10.0 / EXP_MIN_VALUE
+    } else {
+        // This essentially rounds the mantissa for very high numbers.
+        ((value / power_of_10(exponent as i32)) * 1e15).round() / 1e15
+    };
+
+    normalize(mantissa, exponent)
+}
+
+/// The parts of `from_mantissa_exponent(mantissa, exponent)`.
+fn from_mantissa_exponent(mantissa: f64, exponent: f64) -> Parts {
+    if !f64::is_finite(mantissa) || !f64::is_finite(exponent) {
+        return NAN;
+    }
+    normalize(mantissa, exponent)
+}
+
+/// Brings the mantissa into the range from 1 to 10 (exclusive), moving the difference into the exponent.
+#[inline]
+pub(crate) fn normalize(mantissa: f64, exponent: f64) -> Parts {
+    if (1.0..10.0).contains(&mantissa) {
+        return (mantissa, exponent);
+    } else if mantissa == 0.0 {
+        return ZERO;
+    }
+
+    let temp_exponent = mantissa.abs().log10().floor();
+    let mantissa = if (temp_exponent as i32) == NUMBER_EXP_MIN {
+        mantissa * 10.0 / 1e-323
+    } else {
+        mantissa / power_of_10(temp_exponent as i32)
+    };
+    (mantissa, exponent + temp_exponent)
+}
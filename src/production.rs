@@ -0,0 +1,234 @@
+//! Offline progress for chains of producers, where every tier produces the tier below it
+//! (like the dimensions of Antimatter Dimensions).
+//!
+//! With constant rates, the chain is advanced in closed form: after a time t,
+//! tier i holds the sum of `amounts[i + k] * rates[i] * ... * rates[i + k - 1] * t^k / k!`.
+//! Rates which depend on the amounts (multipliers growing with the currency, say)
+//! are handled by adaptive steps, with the rates frozen over each step.
+//!
+//! ```rust
+//! use break_infinity_extended::{production::*, Decimal};
+//!
+//! // currency, first tier, second tier; each unit of a tier produces 2 of the one below per second
+//! let amounts = [Decimal::new(0.0), Decimal::new(0.0), Decimal::new(1.0)];
+//! let rates = [Decimal::new(2.0), Decimal::new(2.0)];
+//!
+//! let progress = Integrator::default().advance(&amounts, &rates, &Decimal::new(10.0));
+//! // 1 * 2 * 2 * 10^2 / 2!
+//! assert_eq!(progress.amounts[0], Decimal::new(200.0));
+//! assert_eq!(progress.amounts[1], Decimal::new(20.0));
+//! ```
+
+use crate::{calculations::sum_compensated, consts::*, Decimal};
+use alloc::vec::Vec;
+
+/// The amounts of every tier at some point in time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checkpoint {
+    pub time: Decimal,
+    pub amounts: Vec<Decimal>,
+}
+
+/// The amounts after the whole time, and the checkpoints on the way there (if any were asked for).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Progress {
+    pub amounts: Vec<Decimal>,
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+/// Advances chains of producers: `amounts[0]` is the currency, and every unit of `amounts[i + 1]`
+/// produces `rates[i]` of `amounts[i]` per unit of time, so there is one rate less than amounts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Integrator {
+    /// Evenly spaced checkpoints to record, the last one at the end of the time.
+    pub checkpoints: usize,
+    /// The relative error per step that `advance_with` aims for.
+    pub tolerance: f64,
+    /// Steps after which `advance_with` stops refining and takes the rest of the time
+    /// in a single step, trading the tolerance for time.
+    pub max_steps: u64,
+}
+
+impl Default for Integrator {
+    fn default() -> Self {
+        Integrator {
+            checkpoints: 0,
+            tolerance: 1e-9,
+            max_steps: 1_000_000,
+        }
+    }
+}
+
+impl Integrator {
+    /// Advances the chain by `dt` with constant rates, exactly.
+    ///
+    /// # Panics
+    ///
+    /// If there isn't exactly one rate less than amounts.
+    pub fn advance(&self, amounts: &[Decimal], rates: &[Decimal], dt: &Decimal) -> Progress {
+        check_lengths(amounts, rates);
+        let checkpoints = self
+            .sample_times(dt)
+            .into_iter()
+            .map(|time| Checkpoint {
+                amounts: advance_chain(amounts, rates, &time),
+                time,
+            })
+            .collect();
+        Progress {
+            amounts: advance_chain(amounts, rates, dt),
+            checkpoints,
+        }
+    }
+
+    /// Advances the chain by `dt`, with rates depending on the amounts.
+    ///
+    /// Every step keeps the rates at their value from the start of the step, and is checked
+    /// against two half steps: steps with a larger relative difference than `tolerance` are
+    /// halved, those well within it doubled. Accepted steps are extrapolated from both results,
+    /// which leaves an error far below the difference between them.
+    ///
+    /// Where the difference can't be measured, as with NaN rates, there is nothing to refine
+    /// and the rest of the time is taken in a single step.
+    ///
+    /// # Panics
+    ///
+    /// If `rates` doesn't return exactly one rate less than amounts.
+    pub fn advance_with<F>(&self, amounts: &[Decimal], rates: F, dt: &Decimal) -> Progress
+    where
+        F: Fn(&[Decimal]) -> Vec<Decimal>,
+    {
+        let frozen_step = |amounts: &[Decimal], step: f64| {
+            let rates = rates(amounts);
+            check_lengths(amounts, &rates);
+            advance_chain(amounts, &rates, &Decimal::new(step))
+        };
+
+        let mut state = amounts.to_vec();
+        let mut time = 0.0;
+        let mut step = dt.to_number();
+        let mut steps = 0;
+        let mut checkpoints = Vec::with_capacity(self.checkpoints);
+        let mut ends = self.sample_times(dt);
+        if ends.last() != Some(dt) {
+            ends.push(*dt);
+        }
+
+        for end in ends {
+            let end_time = end.to_number();
+            while time < end_time {
+                if steps >= self.max_steps {
+                    step = end_time - time;
+                }
+                step = step.min(end_time - time);
+                let full = frozen_step(&state, step);
+                let half = frozen_step(&state, step / 2.0);
+                let halves = frozen_step(&half, step / 2.0);
+                let error = relative_difference(&full, &halves);
+
+                steps += 1;
+                if !error.is_finite() {
+                    state = frozen_step(&state, end_time - time);
+                    break;
+                }
+                if error <= self.tolerance
+                    || steps >= self.max_steps
+                    || step <= end_time * f64::EPSILON
+                {
+                    // the error of frozen rates shrinks with the step, so two half steps
+                    // are twice as close as one full step: extrapolate past them
+                    state = extrapolate(&full, &halves);
+                    time += step;
+                    if error < self.tolerance / 4.0 {
+                        step *= 2.0;
+                    }
+                } else {
+                    step /= 2.0;
+                }
+            }
+            time = end_time;
+            if checkpoints.len() < self.checkpoints {
+                checkpoints.push(Checkpoint {
+                    time: end,
+                    amounts: state.clone(),
+                });
+            }
+        }
+
+        Progress {
+            amounts: state,
+            checkpoints,
+        }
+    }
+
+    fn sample_times(&self, dt: &Decimal) -> Vec<Decimal> {
+        let count = self.checkpoints as f64;
+        (1..=self.checkpoints)
+            .map(|i| {
+                if i == self.checkpoints {
+                    *dt
+                } else {
+                    dt * (i as f64 / count)
+                }
+            })
+            .collect()
+    }
+}
+
+fn check_lengths(amounts: &[Decimal], rates: &[Decimal]) {
+    assert_eq!(
+        rates.len() + 1,
+        amounts.len(),
+        "a chain of {} amounts needs {} rates",
+        amounts.len(),
+        amounts.len().saturating_sub(1)
+    );
+}
+
+// the closed form for constant rates
+fn advance_chain(amounts: &[Decimal], rates: &[Decimal], time: &Decimal) -> Vec<Decimal> {
+    (0..amounts.len())
+        .map(|tier| {
+            // rates[tier] * ... * rates[tier + k - 1] * time^k / k!
+            let mut coefficient = ONE;
+            let terms = amounts[tier..].iter().enumerate().map(|(k, amount)| {
+                let term = amount * coefficient;
+                if let Some(rate) = rates.get(tier + k) {
+                    coefficient = coefficient * rate * time / Decimal::new(k as f64 + 1.0);
+                }
+                term
+            });
+            sum_compensated(terms)
+        })
+        .collect()
+}
+
+// twice the two half steps, minus the full step
+fn extrapolate(full: &[Decimal], halves: &[Decimal]) -> Vec<Decimal> {
+    full.iter()
+        .zip(halves)
+        .map(|(full, halves)| halves * TWO - full)
+        .collect()
+}
+
+// the largest difference relative to the amounts, zero for equal amounts and NaN if any is NaN
+fn relative_difference(first: &[Decimal], second: &[Decimal]) -> f64 {
+    first
+        .iter()
+        .zip(second)
+        .map(|(a, b)| {
+            if a == b {
+                0.0
+            } else {
+                ((a - b).abs() / a.abs().max(&b.abs())).to_number()
+            }
+        })
+        .fold(0.0, |largest, difference| {
+            if largest.is_nan() || difference <= largest {
+                largest
+            } else {
+                // also takes over NaN, which `f64::max` would skip
+                difference
+            }
+        })
+}
@@ -0,0 +1,312 @@
+//! Representations of Decimals for serde, to be used with `#[serde(with = "...")]`.
+//!
+//! - `as_struct`: `{"mantissa": 1.5, "exponent": 300.0}`, the default
+//! - `as_string`: `"1.5e300"`, like the saves of break_infinity.js
+//! - `as_tuple`: `[1.5, 300.0]`
+//! - `as_f64_lossy`: `1.5e300`, which only holds values within the range of `f64`
+//!
+//! The representation of `Decimal` itself can be switched to a string with the `serde-string`
+//! feature, or to a tuple with `serde-tuple`. As features add up across a dependency tree,
+//! both can end up enabled together; then the tuple wins.
+//!
+//! Deserialization is lenient: in human-readable formats (like JSON) every module accepts any of
+//! these forms, so saves keep loading after switching between them. Binary formats usually can't
+//! tell the forms apart, so there the value has to be in the form of the module reading it.
+//!
+//! ```rust
+//! use break_infinity_extended::{from_mantissa_exponent, Decimal};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Save {
+//!     #[serde(with = "break_infinity_extended::representation::as_string")]
+//!     antimatter: Decimal,
+//!     #[serde(with = "break_infinity_extended::representation::as_tuple")]
+//!     infinity_points: Decimal,
+//! }
+//!
+//! let save = Save {
+//!     antimatter: from_mantissa_exponent(1.5, 300.0),
+//!     infinity_points: Decimal::new(42.0),
+//! };
+//! let json = serde_json::to_string(&save).unwrap();
+//! assert_eq!(json, r#"{"antimatter":"1.5e300","infinity_points":[4.2,1.0]}"#);
+//!
+//! // a break_infinity.js save, with a struct where a string is expected
+//! let json = r#"{"antimatter":"1.5e+300","infinity_points":{"mantissa":4.2,"exponent":1}}"#;
+//! let loaded: Save = serde_json::from_str(json).unwrap();
+//! assert_eq!(loaded.antimatter, save.antimatter);
+//! assert_eq!(loaded.infinity_points, save.infinity_points);
+//! ```
+
+use crate::{
+    consts::*, error::TryFromPartsError, utils::from_mantissa_exponent_no_normalize, Decimal,
+};
+use core::fmt::{self, Display, Formatter};
+use serde::{
+    de::{self, IgnoredAny, MapAccess, SeqAccess, Unexpected, Visitor},
+    ser::{SerializeStruct, SerializeTuple},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+const FIELDS: &[&str] = &["mantissa", "exponent"];
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[cfg(feature = "serde-tuple")]
+        return as_tuple::serialize(self, serializer);
+        #[cfg(all(feature = "serde-string", not(feature = "serde-tuple")))]
+        return as_string::serialize(self, serializer);
+        #[cfg(not(any(feature = "serde-string", feature = "serde-tuple")))]
+        return as_struct::serialize(self, serializer);
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+        #[cfg(feature = "serde-tuple")]
+        return as_tuple::deserialize(deserializer);
+        #[cfg(all(feature = "serde-string", not(feature = "serde-tuple")))]
+        return as_string::deserialize(deserializer);
+        #[cfg(not(any(feature = "serde-string", feature = "serde-tuple")))]
+        return as_struct::deserialize(deserializer);
+    }
+}
+
+/// A Decimal as a struct of its mantissa and exponent, exactly as stored.
+///
+/// Parts which aren't in the canonical form (see `Decimal::is_normalized`) are normalized
+/// when read, and rejected if they can't be, like non-finite parts or too large exponents.
+pub mod as_struct {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Decimal", 2)?;
+        state.serialize_field("mantissa", &value.mantissa)?;
+        state.serialize_field("exponent", &value.exponent)?;
+        state.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+        lenient(deserializer, |deserializer| {
+            deserializer.deserialize_struct("Decimal", FIELDS, DecimalVisitor)
+        })
+    }
+}
+
+/// A Decimal as a string like `1.5e300`, which break_infinity.js can read as well.
+///
+/// The mantissa is written with as many digits as it takes to read back the very same value;
+/// `NaN` and `Infinity` are written as such, and infinities are read back as `MAX` and `MIN`.
+pub mod as_string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&Exact(value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+        lenient(deserializer, |deserializer| {
+            deserializer.deserialize_str(DecimalVisitor)
+        })
+    }
+}
+
+/// A Decimal as a tuple of its mantissa and exponent, exactly as stored;
+/// read like the struct of `as_struct`.
+pub mod as_tuple {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_tuple(2)?;
+        state.serialize_element(&value.mantissa)?;
+        state.serialize_element(&value.exponent)?;
+        state.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+        lenient(deserializer, |deserializer| {
+            deserializer.deserialize_tuple(2, DecimalVisitor)
+        })
+    }
+}
+
+/// A Decimal as an `f64`; values beyond its range become infinite or zero,
+/// and the rest may lose some precision.
+///
+/// JSON has no NaN or infinities, so serde_json writes them as `null`, which is read back as NaN.
+pub mod as_f64_lossy {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(value.to_number())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+        lenient(deserializer, |deserializer| {
+            deserializer.deserialize_f64(DecimalVisitor)
+        })
+    }
+}
+
+// human-readable formats are self-describing, so any form can be accepted there;
+// otherwise the form has to be given
+fn lenient<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    form: fn(D) -> Result<Decimal, D::Error>,
+) -> Result<Decimal, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(DecimalVisitor)
+    } else {
+        form(deserializer)
+    }
+}
+
+// the shortest mantissa which reads back exactly
+struct Exact<'a>(&'a Decimal);
+
+impl Display for Exact<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Decimal { mantissa, exponent } = *self.0;
+        if mantissa.is_nan() || exponent.is_nan() {
+            write!(f, "NaN")
+        } else if exponent.is_infinite() {
+            write!(
+                f,
+                "{}",
+                if mantissa > 0.0 {
+                    "Infinity"
+                } else {
+                    "-Infinity"
+                }
+            )
+        } else if mantissa == 0.0 {
+            write!(f, "0")
+        } else {
+            write!(f, "{mantissa}e{exponent}")
+        }
+    }
+}
+
+struct DecimalVisitor;
+
+impl<'de> Visitor<'de> for DecimalVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("a Decimal as a string, a number, a (mantissa, exponent) tuple or struct")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Decimal, E> {
+        value.parse().map_err(E::custom)
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Decimal, E> {
+        Ok(Decimal::new(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Decimal, E> {
+        Ok(Decimal::new(value as f64))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Decimal, E> {
+        Ok(Decimal::new(value as f64))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Decimal, E> {
+        Ok(NAN)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Decimal, A::Error> {
+        let mantissa = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let exponent = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        from_parts(mantissa, exponent)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Decimal, A::Error> {
+        let mut mantissa = None;
+        let mut exponent = None;
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Mantissa if mantissa.is_some() => {
+                    return Err(de::Error::duplicate_field("mantissa"))
+                }
+                Field::Exponent if exponent.is_some() => {
+                    return Err(de::Error::duplicate_field("exponent"))
+                }
+                Field::Mantissa => mantissa = Some(map.next_value()?),
+                Field::Exponent => exponent = Some(map.next_value()?),
+                Field::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        from_parts(
+            mantissa.ok_or_else(|| de::Error::missing_field("mantissa"))?,
+            exponent.ok_or_else(|| de::Error::missing_field("exponent"))?,
+        )
+    }
+}
+
+// the parts as they are if they are canonical, so that every Decimal reads back exactly,
+// and normalized otherwise
+fn from_parts<E: de::Error>(mantissa: f64, exponent: f64) -> Result<Decimal, E> {
+    let decimal = from_mantissa_exponent_no_normalize(mantissa, exponent);
+    if decimal.is_normalized() {
+        return Ok(decimal);
+    }
+    Decimal::try_from_parts(mantissa, exponent).map_err(|error| match error {
+        TryFromPartsError::NonFiniteMantissa => {
+            E::invalid_value(Unexpected::Float(mantissa), &"a finite mantissa")
+        }
+        TryFromPartsError::NonFiniteExponent => {
+            E::invalid_value(Unexpected::Float(exponent), &"a finite exponent")
+        }
+        TryFromPartsError::ExponentOutOfRange => E::invalid_value(
+            Unexpected::Float(exponent),
+            &"an exponent within EXP_LIMIT after normalization",
+        ),
+    })
+}
+
+enum Field {
+    Mantissa,
+    Exponent,
+    Other,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Field, D::Error> {
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct FieldVisitor;
+
+impl<'de> Visitor<'de> for FieldVisitor {
+    type Value = Field;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("`mantissa` or `exponent`")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+        Ok(match value {
+            "mantissa" => Field::Mantissa,
+            "exponent" => Field::Exponent,
+            _ => Field::Other,
+        })
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Field, E> {
+        Ok(match value {
+            0 => Field::Mantissa,
+            1 => Field::Exponent,
+            _ => Field::Other,
+        })
+    }
+}
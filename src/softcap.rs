@@ -0,0 +1,197 @@
+//! Softcaps and hardcaps for balancing: above a threshold, a value grows slower (or not at all).
+//!
+//! Every cap is continuous and non-decreasing, and can be inverted to answer
+//! "how much raw input is needed for this output?".
+//!
+//! ```rust
+//! use break_infinity_extended::{softcap::*, Decimal};
+//!
+//! let cap = StackedSoftcap::new()
+//!     .then(PowerSoftcap { start: Decimal::new(100.0), power: 0.5 })
+//!     .then(Hardcap { limit: Decimal::new(1000.0) });
+//!
+//! assert_eq!(cap.apply(&Decimal::new(50.0)), Decimal::new(50.0));
+//! assert_eq!(cap.apply(&Decimal::new(10000.0)), Decimal::new(1000.0));
+//! assert_eq!(cap.invert(&Decimal::new(1000.0)), Decimal::new(10000.0));
+//! assert!(cap.invert(&Decimal::new(2000.0)).is_nan());
+//! ```
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::F64Ext;
+use crate::{
+    consts::*,
+    utils::{exp_decimal, pow10_decimal},
+    Decimal,
+};
+use alloc::{sync::Arc, vec::Vec};
+use core::fmt::Debug;
+
+/// A cap on a value, together with its inverse.
+pub trait Softcap: Debug {
+    /// Applies the cap; values up to the threshold pass unchanged.
+    fn apply(&self, value: &Decimal) -> Decimal;
+
+    /// The smallest raw value which `apply` turns into the given output,
+    /// or NaN if no raw value reaches it.
+    fn invert(&self, value: &Decimal) -> Decimal;
+}
+
+/// Above `start`, the value is raised to `power` (between 0 and 1): start * (value / start)^power.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PowerSoftcap {
+    pub start: Decimal,
+    pub power: f64,
+}
+
+impl Softcap for PowerSoftcap {
+    fn apply(&self, value: &Decimal) -> Decimal {
+        if value <= &self.start {
+            return *value;
+        }
+        let log_start = self.start.log10();
+        pow10_decimal(log_start + (value.log10() - log_start) * self.power)
+    }
+
+    fn invert(&self, value: &Decimal) -> Decimal {
+        if value <= &self.start {
+            return *value;
+        }
+        let log_start = self.start.log10();
+        pow10_decimal(log_start + (value.log10() - log_start) / self.power)
+    }
+}
+
+/// Above `start`, the value only grows logarithmically: start * (1 + ln(value / start)).
+///
+/// The slope is continuous at `start`, so there's no visible kink.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogarithmicSoftcap {
+    pub start: Decimal,
+}
+
+impl Softcap for LogarithmicSoftcap {
+    fn apply(&self, value: &Decimal) -> Decimal {
+        if value <= &self.start {
+            return *value;
+        }
+        self.start * ((value / self.start).ln_decimal() + ONE)
+    }
+
+    fn invert(&self, value: &Decimal) -> Decimal {
+        if value <= &self.start {
+            return *value;
+        }
+        self.start * exp_decimal(&(value / self.start - ONE))
+    }
+}
+
+/// Above `start` (which has to be greater than one), the orders of magnitude are raised to `power`
+/// (between 0 and 1), as in 10^(log10(value)^power), scaled so that `start` stays in place:
+/// 10^(log10(start) * (log10(value) / log10(start))^power).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DilationSoftcap {
+    pub start: Decimal,
+    pub power: f64,
+}
+
+impl Softcap for DilationSoftcap {
+    fn apply(&self, value: &Decimal) -> Decimal {
+        if value <= &self.start {
+            return *value;
+        }
+        let log_start = self.start.log10();
+        pow10_decimal(log_start * (value.log10() / log_start).powf(self.power))
+    }
+
+    fn invert(&self, value: &Decimal) -> Decimal {
+        if value <= &self.start {
+            return *value;
+        }
+        let log_start = self.start.log10();
+        pow10_decimal(log_start * (value.log10() / log_start).powf(1.0 / self.power))
+    }
+}
+
+/// Above `start`, the excess is divided by `divisor` (at least one): start + (value - start) / divisor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DivisionSoftcap {
+    pub start: Decimal,
+    pub divisor: Decimal,
+}
+
+impl Softcap for DivisionSoftcap {
+    fn apply(&self, value: &Decimal) -> Decimal {
+        if value <= &self.start {
+            return *value;
+        }
+        self.start + (value - self.start) / self.divisor
+    }
+
+    fn invert(&self, value: &Decimal) -> Decimal {
+        if value <= &self.start {
+            return *value;
+        }
+        self.start + (value - self.start) * self.divisor
+    }
+}
+
+/// The value can't exceed `limit`; only `limit` itself can be inverted above it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hardcap {
+    pub limit: Decimal,
+}
+
+impl Softcap for Hardcap {
+    fn apply(&self, value: &Decimal) -> Decimal {
+        if value > &self.limit {
+            self.limit
+        } else {
+            *value
+        }
+    }
+
+    fn invert(&self, value: &Decimal) -> Decimal {
+        if value <= &self.limit {
+            *value
+        } else {
+            NAN
+        }
+    }
+}
+
+/// Several caps applied one after another; each threshold refers to the output of the caps before it.
+///
+/// Clones share the caps, which are never modified once added; the caps have to be
+/// `Send + Sync`, so the stack can be shared between threads.
+#[derive(Clone, Debug, Default)]
+pub struct StackedSoftcap {
+    caps: Vec<Arc<dyn Softcap + Send + Sync>>,
+}
+
+impl StackedSoftcap {
+    /// Creates an empty stack, which leaves values unchanged.
+    pub fn new() -> StackedSoftcap {
+        StackedSoftcap::default()
+    }
+
+    /// Adds a cap which is applied after the ones already in the stack.
+    pub fn then<S: Softcap + Send + Sync + 'static>(mut self, cap: S) -> StackedSoftcap {
+        self.caps.push(Arc::new(cap));
+        self
+    }
+}
+
+impl Softcap for StackedSoftcap {
+    fn apply(&self, value: &Decimal) -> Decimal {
+        self.caps
+            .iter()
+            .fold(*value, |value, cap| cap.apply(&value))
+    }
+
+    fn invert(&self, value: &Decimal) -> Decimal {
+        self.caps
+            .iter()
+            .rev()
+            .fold(*value, |value, cap| cap.invert(&value))
+    }
+}
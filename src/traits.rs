@@ -1,6 +1,10 @@
 use crate::{consts::*, Decimal};
-use std::fmt::{Display, Formatter, Result};
+use core::{
+    fmt::{Display, Formatter, Result},
+    iter::{Product, Sum},
+};
 
+/// Infinite values (see `Decimal`) are displayed as `Infinity` and `-Infinity`.
 impl Display for Decimal {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         if f64::is_nan(self.mantissa) || f64::is_nan(self.exponent) {
@@ -30,3 +34,28 @@ impl Display for Decimal {
         write!(f, "{}", form)
     }
 }
+
+/// Plain left-to-right summation; see `sum_compensated` for sums of many small values.
+impl Sum for Decimal {
+    fn sum<I: Iterator<Item = Decimal>>(iter: I) -> Decimal {
+        iter.fold(ZERO, |acc, decimal| acc + decimal)
+    }
+}
+
+impl<'a> Sum<&'a Decimal> for Decimal {
+    fn sum<I: Iterator<Item = &'a Decimal>>(iter: I) -> Decimal {
+        iter.fold(ZERO, |acc, decimal| acc + decimal)
+    }
+}
+
+impl Product for Decimal {
+    fn product<I: Iterator<Item = Decimal>>(iter: I) -> Decimal {
+        iter.fold(ONE, |acc, decimal| acc * decimal)
+    }
+}
+
+impl<'a> Product<&'a Decimal> for Decimal {
+    fn product<I: Iterator<Item = &'a Decimal>>(iter: I) -> Decimal {
+        iter.fold(ONE, |acc, decimal| acc * decimal)
+    }
+}
@@ -1,4 +1,28 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::F64Ext;
 use crate::{consts::*, Decimal};
+use alloc::{format, string::String};
+use core::f64::consts::{E, LOG10_E, PI};
+
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+const SQRT_2PI: f64 = 2.506_628_274_631_000_2;
+pub(crate) const LN_SQRT_2PI: f64 = 0.918_938_533_204_672_8;
+/// -1/e, the smallest argument of the Lambert W function.
+pub(crate) const LAMBERT_W_MIN: f64 = -0.367_879_441_171_442_33;
+/// `Decimal::new` rounds to 15 digits, which can push -1/e just below `LAMBERT_W_MIN`;
+/// anything that close still counts as the branch point.
+pub(crate) const LAMBERT_W_TOLERANCE: f64 = 1e-15;
 
 /// Returns the power of 10 with the given exponent from the cache.
 #[inline]
@@ -48,7 +72,7 @@ pub fn to_fixed_num(num: f64, places: u32) -> f64 {
 
 /// Creates a new instance of Decimal with the given mantissa and exponent without normalizing them.
 #[inline]
-pub fn from_mantissa_exponent_no_normalize(mantissa: f64, exponent: f64) -> Decimal {
+pub const fn from_mantissa_exponent_no_normalize(mantissa: f64, exponent: f64) -> Decimal {
     Decimal { mantissa, exponent }
 }
 
@@ -63,18 +87,129 @@ pub fn from_mantissa_exponent(mantissa: f64, exponent: f64) -> Decimal {
 
 #[inline]
 pub(crate) fn normalize_mantissa_and_exponent(mantissa: f64, exponent: f64) -> Decimal {
-    if mantissa >= 1.0 && mantissa < 10.0 {
-        return Decimal { mantissa, exponent };
-    } else if mantissa == 0.0 {
-        return ZERO;
+    let (mantissa, exponent) = crate::parts::normalize(mantissa, exponent);
+    Decimal { mantissa, exponent }
+}
+
+/// The gamma function for f64, using the Lanczos approximation (about 15 significant digits)
+/// and the reflection formula below 0.5.
+pub(crate) fn gamma_f64(x: f64) -> f64 {
+    if x < 0.5 {
+        return PI / ((PI * x).sin() * gamma_f64(1.0 - x));
+    }
+    let x = x - 1.0;
+    let t = x + LANCZOS_G + 0.5;
+    // t^(x + 0.5) alone overflows long before the result does
+    let power = t.powf((x + 0.5) / 2.0);
+    SQRT_2PI * power * ((-t).exp() * power) * lanczos_sum(x)
+}
+
+/// The natural logarithm of the absolute value of the gamma function for f64, see `gamma_f64`.
+pub(crate) fn ln_gamma_f64(x: f64) -> f64 {
+    if x < 0.5 {
+        return (PI / (PI * x).sin().abs()).ln() - ln_gamma_f64(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + LANCZOS_G + 0.5;
+    LN_SQRT_2PI + (x + 0.5) * t.ln() - t + lanczos_sum(x).ln()
+}
+
+#[inline]
+fn lanczos_sum(x: f64) -> f64 {
+    LANCZOS_COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(LANCZOS_COEFFICIENTS[0], |sum, (i, coefficient)| {
+            sum + coefficient / (x + i as f64 + 1.0)
+        })
+}
+
+/// e^power, also for powers beyond the range of f64 (where `Decimal::exp` gives up).
+pub(crate) fn exp_decimal(power: &Decimal) -> Decimal {
+    pow10_decimal((power * LOG10_E).to_number())
+}
+
+/// 10^power as a Decimal, saturating to `MAX` and `ZERO` at the limits of the exponent.
+pub(crate) fn pow10_decimal(power: f64) -> Decimal {
+    if f64::is_nan(power) {
+        NAN
+    } else if power >= EXP_LIMIT {
+        MAX
+    } else if power <= -EXP_LIMIT {
+        ZERO
+    } else {
+        let exponent = power.floor();
+        from_mantissa_exponent(10.0_f64.powf(power - exponent), exponent)
     }
+}
 
-    let temp_exponent = mantissa.abs().log10().floor();
-    let mantissa = if (temp_exponent as i32) == NUMBER_EXP_MIN {
-        mantissa * 10.0 / 1e-323
+/// The principal branch W₀ of the Lambert W function (w * e^w = x) for f64, x >= -1/e,
+/// using Halley's method.
+pub(crate) fn lambert_w0_f64(x: f64) -> f64 {
+    if f64::is_nan(x) || x < LAMBERT_W_MIN - LAMBERT_W_TOLERANCE {
+        return f64::NAN;
+    }
+    if f64::is_infinite(x) {
+        return x;
+    }
+    if x > 1e100 {
+        // w * e^w overflows long before x does
+        let ln = x.ln();
+        return lambert_w_from_ln(ln, ln - ln.ln());
+    }
+    let guess = if x < 0.0 {
+        // series expansion around the branch point
+        let p = (2.0 * (E * x + 1.0)).sqrt();
+        -1.0 + p - p * p / 3.0
+    } else if x < 3.0 {
+        x / (1.0 + x)
     } else {
-        mantissa / power_of_10(temp_exponent as i32)
+        x.ln() - x.ln().ln()
     };
-    let exponent = exponent + temp_exponent;
-    Decimal { mantissa, exponent }
+    lambert_w_halley(x, guess)
+}
+
+/// The lower branch W₋₁ of the Lambert W function (w * e^w = x, w <= -1) for f64, -1/e <= x < 0.
+pub(crate) fn lambert_w_m1_f64(x: f64) -> f64 {
+    if f64::is_nan(x) || !(LAMBERT_W_MIN - LAMBERT_W_TOLERANCE..0.0).contains(&x) {
+        return f64::NAN;
+    }
+    if x >= -0.25 {
+        let ln = (-x).ln();
+        return lambert_w_from_ln(ln, ln - (-ln).ln());
+    }
+    // series expansion around the branch point
+    let p = -(2.0 * (E * x + 1.0)).sqrt();
+    lambert_w_halley(x, -1.0 + p - p * p / 3.0)
+}
+
+/// Solves w + ln(|w|) = ln_x, the logarithm of w * e^w = x, with Newton's method;
+/// away from the branch point this works for arguments far beyond the range of f64.
+pub(crate) fn lambert_w_from_ln(ln_x: f64, guess: f64) -> f64 {
+    let mut w = guess;
+    for _ in 0..100 {
+        let step = (w + w.abs().ln() - ln_x) / (1.0 + 1.0 / w);
+        w -= step;
+        if !f64::is_finite(w) || step.abs() <= f64::EPSILON * w.abs() {
+            break;
+        }
+    }
+    w
+}
+
+fn lambert_w_halley(x: f64, guess: f64) -> f64 {
+    if x <= LAMBERT_W_MIN {
+        return -1.0;
+    }
+    let mut w = guess;
+    for _ in 0..100 {
+        let ew = w.exp();
+        let f = w * ew - x;
+        let step = f / (ew * (w + 1.0) - (w + 2.0) * f / (2.0 * w + 2.0));
+        w -= step;
+        if !f64::is_finite(w) || step.abs() <= f64::EPSILON * (1.0 + w.abs()) {
+            break;
+        }
+    }
+    w
 }
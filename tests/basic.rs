@@ -32,7 +32,35 @@ fn formatters() {
     assert_eq!(format!("{:b}", Decimal::new(1e12)), "1 T");
     assert_eq!(format!("{:b}", Decimal::new(1e15)), "1 Qa");
     assert_eq!(format!("{:b}", Decimal::new(1e153)), "1 Qq");
-    assert_eq!(format!("{:.3b}", Decimal::new(1e154)), "1.000e+154");
+    assert_eq!(format!("{:.3b}", Decimal::new(1e154)), "10.000 Qq");
+    assert_eq!(format!("{:b}", Decimal::new(1e183)), "1 Sg");
+    assert_eq!(format!("{:b}", Decimal::new(1e303)), "1 Ct");
+    assert_eq!(
+        format!("{:b}", from_mantissa_exponent(1.0, 336.0)),
+        "1 UDcCt"
+    );
+    assert_eq!(format!("{:b}", from_mantissa_exponent(1.0, 603.0)), "1 De");
+    assert_eq!(format!("{:b}", from_mantissa_exponent(1.0, 3003.0)), "1 Mi");
+    assert_eq!(
+        format!("{:b}", from_mantissa_exponent(1.0, 3006.0)),
+        "1 MiU"
+    );
+    assert_eq!(
+        format!("{:b}", from_mantissa_exponent(1.0, 6009.0)),
+        "1 DMiD"
+    );
+    assert_eq!(
+        format!("{:b}", from_mantissa_exponent(1.0, 3e6 + 3.0)),
+        "1 Mc"
+    );
+    assert_eq!(
+        format!("{:.3b}", from_mantissa_exponent(1.0, 1e16)),
+        "1.000e+10000000000000000"
+    );
+    for exponent in 0..10_000 {
+        let number = from_mantissa_exponent(1.5, exponent as f64);
+        assert!(!format!("{number:b}").contains("e+"));
+    }
 }
 
 #[test]
@@ -165,3 +193,1115 @@ fn constants() {
     assert_eq!(consts::TAU.to_number(), std::f64::consts::TAU);
     assert_eq!(consts::E.to_number(), std::f64::consts::E);
 }
+
+#[test]
+fn parse() {
+    assert_eq!("123.45".parse::<Decimal>(), Ok(Decimal::new(123.45)));
+    assert_eq!(
+        "1.5e1000".parse::<Decimal>(),
+        Ok(from_mantissa_exponent(1.5, 1000.0))
+    );
+    assert_eq!("1E+5".parse::<Decimal>(), Ok(Decimal::new(1e5)));
+    assert_eq!("1e-5".parse::<Decimal>(), Ok(Decimal::new(1e-5)));
+    assert_eq!("+42".parse::<Decimal>(), Ok(Decimal::new(42.0)));
+    assert_eq!("  -7e2 \n".parse::<Decimal>(), Ok(Decimal::new(-700.0)));
+    assert_eq!("0e999".parse::<Decimal>(), Ok(consts::ZERO));
+    assert_eq!("Infinity".parse::<Decimal>(), Ok(consts::MAX));
+    assert_eq!("-Infinity".parse::<Decimal>(), Ok(consts::MIN));
+    assert!("NaN".parse::<Decimal>().unwrap().to_string() == "NaN");
+    assert!("1"
+        .repeat(400)
+        .parse::<Decimal>()
+        .unwrap()
+        .to_string()
+        .ends_with("e+399"));
+
+    assert_eq!("".parse::<Decimal>(), Err(ParseDecimalError::Empty));
+    assert_eq!("   ".parse::<Decimal>(), Err(ParseDecimalError::Empty));
+    assert_eq!(
+        "abc".parse::<Decimal>(),
+        Err(ParseDecimalError::InvalidMantissa { offset: 0 })
+    );
+    assert_eq!(
+        " e5".parse::<Decimal>(),
+        Err(ParseDecimalError::InvalidMantissa { offset: 1 })
+    );
+    assert_eq!(
+        "1e".parse::<Decimal>(),
+        Err(ParseDecimalError::InvalidExponent { offset: 2 })
+    );
+    assert_eq!(
+        "1e+x".parse::<Decimal>(),
+        Err(ParseDecimalError::InvalidExponent { offset: 2 })
+    );
+    assert_eq!(
+        "1e1e400".parse::<Decimal>(),
+        Err(ParseDecimalError::ExponentOutOfRange { offset: 2 })
+    );
+    assert_eq!(
+        "12abc".parse::<Decimal>(),
+        Err(ParseDecimalError::TrailingCharacters { offset: 2 })
+    );
+    assert_eq!(
+        "1.5e10 apples".parse::<Decimal>(),
+        Err(ParseDecimalError::TrailingCharacters { offset: 6 })
+    );
+
+    assert_eq!(Decimal::from("1e5"), Decimal::new(1e5));
+}
+
+#[test]
+#[should_panic]
+fn from_invalid_str() {
+    let _ = Decimal::from("garbage");
+}
+
+#[test]
+fn notations() {
+    use break_infinity_extended::notation::*;
+
+    let value = from_mantissa_exponent(1.2345, 1234.0);
+    assert_eq!(ScientificNotation.format(&value, 2), "1.23e1234");
+    assert_eq!(EngineeringNotation.format(&value, 2), "12.35e1233");
+    assert_eq!(StandardNotation.format(&value, 2), "12.35 DcQt");
+    assert_eq!(LettersNotation.format(&value, 2), "12.35ou");
+    assert_eq!(LogarithmNotation.format(&value, 2), "e1234.09");
+    assert_eq!(InfinityNotation.format(&value, 2), "4.00∞");
+
+    let mixed = MixedScientificNotation::default();
+    assert_eq!(mixed.format(&Decimal::new(1.5e30), 1), "1.5 No");
+    assert_eq!(mixed.format(&Decimal::new(1.5e33), 1), "1.5e33");
+
+    assert_eq!(
+        ScientificNotation.format(&Decimal::new(9.999e5), 2),
+        "1.00e6"
+    );
+    assert_eq!(
+        EngineeringNotation.format(&Decimal::new(999.99e3), 1),
+        "1.0e6"
+    );
+    assert_eq!(LettersNotation.format(&Decimal::new(1e3), 0), "1a");
+    assert_eq!(LettersNotation.format(&Decimal::new(1e78), 0), "1z");
+    assert_eq!(LettersNotation.format(&Decimal::new(1e81), 0), "1aa");
+    assert_eq!(
+        ScientificNotation.format(&from_mantissa_exponent(1.0, 1.5e10), 2),
+        "1.00e1.50e10"
+    );
+
+    assert_eq!(
+        ScientificNotation.format(&Decimal::new(123.456), 1),
+        "123.5"
+    );
+    assert_eq!(
+        ScientificNotation.format(&Decimal::new(-1.5e10), 1),
+        "-1.5e10"
+    );
+    assert_eq!(StandardNotation.format(&consts::MAX, 2), "Infinity");
+    assert_eq!(StandardNotation.format(&consts::NAN, 2), "NaN");
+}
+
+#[test]
+fn primitive_ops() {
+    let a = from_mantissa_exponent_no_normalize(3.224, 54.0);
+
+    assert_eq!(a * 2.0, from_mantissa_exponent_no_normalize(6.448, 54.0));
+    assert_eq!(2.0 * a, from_mantissa_exponent_no_normalize(6.448, 54.0));
+    assert_eq!(a * 4, from_mantissa_exponent_no_normalize(1.2896, 55.0));
+    assert_eq!(&a / 2_u64, from_mantissa_exponent_no_normalize(1.612, 54.0));
+    assert_eq!(a * 1e10, from_mantissa_exponent_no_normalize(3.224, 64.0));
+    assert_eq!(a * 0.0, consts::ZERO);
+    assert_eq!(a * f64::INFINITY, a * consts::MAX);
+    assert_eq!(1.0 / Decimal::new(4.0), Decimal::new(0.25));
+
+    assert_eq!(Decimal::new(1.5) + 1.5, Decimal::new(3.0));
+    assert_eq!(1.5 + Decimal::new(1.5), Decimal::new(3.0));
+    assert_eq!(Decimal::new(10.0) - 3_i64, Decimal::new(7.0));
+    assert_eq!(3_u32 - Decimal::new(10.0), Decimal::new(-7.0));
+
+    let mut b = Decimal::new(10.0);
+    b += 5;
+    b -= 1.0;
+    b *= 3_u32;
+    b /= 2_i64;
+    assert!(b.eq_tolerance(&Decimal::new(21.0), &Decimal::new(1e-15)));
+}
+
+#[test]
+fn iterators() {
+    let values = [Decimal::new(1.0), Decimal::new(2.0), Decimal::new(3.0)];
+    assert_eq!(values.iter().sum::<Decimal>(), Decimal::new(6.0));
+    assert_eq!(values.into_iter().sum::<Decimal>(), Decimal::new(6.0));
+    assert_eq!(values.iter().product::<Decimal>(), Decimal::new(6.0));
+    assert_eq!(values.into_iter().product::<Decimal>(), Decimal::new(6.0));
+    assert_eq!(Vec::<Decimal>::new().iter().sum::<Decimal>(), consts::ZERO);
+    assert_eq!(
+        Vec::<Decimal>::new().iter().product::<Decimal>(),
+        consts::ONE
+    );
+
+    let big = Decimal::new(1e20);
+    let contributions = std::iter::once(big).chain(std::iter::repeat_n(consts::ONE, 1_000_000));
+    assert_eq!(contributions.clone().sum::<Decimal>(), big);
+    assert_eq!(
+        sum_compensated(contributions),
+        Decimal::new(1.00000000000001e20)
+    );
+
+    assert_eq!(sum_compensated(values.iter()), Decimal::new(6.0));
+    assert_eq!(sum_compensated(Vec::<Decimal>::new()), consts::ZERO);
+}
+
+#[test]
+fn remainders() {
+    let seven = Decimal::new(7.0);
+    let three = Decimal::new(3.0);
+
+    assert_eq!(seven % three, consts::ONE);
+    assert_eq!(-seven % three, consts::NEG_ONE);
+    assert_eq!(seven % -three, consts::ONE);
+    assert_eq!(Decimal::new(123.0) % 10, Decimal::new(3.0));
+    assert_eq!(Decimal::new(5.5) % 2.0, Decimal::new(1.5));
+    assert_eq!(25 % Decimal::new(7.0), Decimal::new(4.0));
+    assert_eq!(Decimal::new(2.0) % Decimal::new(5.0), Decimal::new(2.0));
+    assert!((seven % consts::ZERO).to_string() == "NaN");
+    // an infinite dividend has no remainder, MAX and MIN included
+    for infinite in [
+        consts::MAX,
+        consts::MIN,
+        consts::INFINITY,
+        consts::NEG_INFINITY,
+    ] {
+        assert!((infinite % seven).is_nan(), "{infinite:?}");
+    }
+    assert_eq!(seven % consts::MAX, seven);
+
+    let mut level = Decimal::new(47.0);
+    level %= 10_u32;
+    assert_eq!(level, seven);
+
+    // beyond f64, within the precision of the dividend
+    assert_eq!(
+        from_mantissa_exponent(1.5, 400.0) % from_mantissa_exponent(1.0, 400.0),
+        from_mantissa_exponent(5.0, 399.0)
+    );
+    // within f64 the result is f64's, even far below the precision of the dividend
+    assert_eq!(Decimal::new(1e20) % three, Decimal::new(1e20 % 3.0));
+    assert_eq!(Decimal::new(1e20) % three, consts::ONE);
+    assert_eq!(
+        Decimal::new(1e300) % seven,
+        Decimal::new(Decimal::new(1e300).to_number() % 7.0)
+    );
+    // beyond f64, the divisor is below the precision floor of the dividend
+    assert_eq!(from_mantissa_exponent(1.0, 1000.0) % seven, consts::ZERO);
+    assert_eq!(
+        from_mantissa_exponent(1.0, 1000.0) % from_mantissa_exponent(3.0, 900.0),
+        consts::ZERO
+    );
+
+    assert_eq!(seven.div_euclid(&three), Decimal::new(2.0));
+    assert_eq!((-seven).div_euclid(&three), Decimal::new(-3.0));
+    assert_eq!(seven.div_euclid(&-three), Decimal::new(-2.0));
+    assert_eq!((-seven).div_euclid(&-three), Decimal::new(3.0));
+
+    assert_eq!(seven.rem_euclid(&three), consts::ONE);
+    assert_eq!((-seven).rem_euclid(&three), consts::TWO);
+    assert_eq!(seven.rem_euclid(&-three), consts::ONE);
+    assert_eq!((-seven).rem_euclid(&-three), consts::TWO);
+
+    assert_eq!(seven.modulo(&three), consts::ONE);
+    assert_eq!((-seven).modulo(&three), consts::TWO);
+    assert_eq!(seven.modulo(&-three), Decimal::new(-2.0));
+    assert_eq!((-seven).modulo(&-three), consts::NEG_ONE);
+    assert_eq!(Decimal::new(-10.0).modulo(&Decimal::new(5.0)), consts::ZERO);
+}
+
+#[test]
+fn total_ordering() {
+    use std::collections::{BTreeMap, HashSet};
+    use std::{cmp::Ordering, hash::BuildHasher};
+
+    let nan = consts::NAN;
+    let other_nan = from_mantissa_exponent_no_normalize(-f64::NAN, 1.0);
+    let zero = consts::ZERO;
+    let neg_zero = from_mantissa_exponent_no_normalize(-0.0, -0.0);
+
+    assert_eq!(nan.total_cmp(&other_nan), Ordering::Equal);
+    assert_eq!(nan.total_cmp(&consts::INFINITY), Ordering::Greater);
+    assert_eq!(consts::INFINITY.total_cmp(&consts::MAX), Ordering::Greater);
+    assert_eq!(zero.total_cmp(&neg_zero), Ordering::Equal);
+    assert_eq!(
+        Decimal::new(-1e100).total_cmp(&Decimal::new(-1e99)),
+        Ordering::Less
+    );
+    assert_eq!(Decimal::new(-1e-100).total_cmp(&zero), Ordering::Less);
+
+    let state = std::collections::hash_map::RandomState::new();
+    assert_eq!(state.hash_one(zero), state.hash_one(neg_zero));
+    assert_eq!(state.hash_one(nan), state.hash_one(other_nan));
+
+    let mut values = [
+        Decimal::new(3.0),
+        nan,
+        Decimal::new(-2.0),
+        consts::MAX,
+        zero,
+        Decimal::new(1e-5),
+        consts::MIN,
+    ];
+    values.sort_by(Decimal::total_cmp);
+    assert_eq!(values[0], consts::MIN);
+    assert_eq!(values[1], Decimal::new(-2.0));
+    assert_eq!(values[2], zero);
+    assert_eq!(values[3], Decimal::new(1e-5));
+    assert_eq!(values[5], consts::MAX);
+    assert_eq!(values[6].to_string(), "NaN");
+
+    let mut totals: Vec<TotalDecimal> = values.iter().copied().map(TotalDecimal).collect();
+    totals.sort();
+    assert_eq!(totals.iter().max(), Some(&TotalDecimal(nan)));
+    assert_eq!(
+        totals.iter().filter(|d| d.0.to_string() != "NaN").max(),
+        Some(&TotalDecimal(consts::MAX))
+    );
+
+    let set: HashSet<TotalDecimal> = [zero, neg_zero, nan, other_nan]
+        .into_iter()
+        .map(TotalDecimal)
+        .collect();
+    assert_eq!(set.len(), 2);
+
+    let mut map = BTreeMap::new();
+    map.insert(TotalDecimal(Decimal::new(10.0)), "ten");
+    map.insert(TotalDecimal(Decimal::new(1.0)), "one");
+    assert_eq!(map.values().collect::<Vec<_>>(), vec![&"one", &"ten"]);
+}
+
+#[test]
+fn classification() {
+    use std::num::FpCategory;
+
+    assert!(consts::NAN.is_nan());
+    assert!(!consts::NAN.is_finite());
+    assert!(!consts::NAN.is_infinite());
+    assert_eq!(consts::NAN.classify(), FpCategory::Nan);
+
+    for infinite in [
+        consts::MAX,
+        consts::MIN,
+        consts::INFINITY,
+        consts::NEG_INFINITY,
+    ] {
+        assert!(infinite.is_infinite());
+        assert!(!infinite.is_finite());
+        assert_eq!(infinite.classify(), FpCategory::Infinite);
+    }
+    assert_eq!(Decimal::new(f64::INFINITY), consts::MAX);
+    assert_eq!(Decimal::from("Infinity"), consts::MAX);
+    assert_eq!(consts::INFINITY.to_string(), "Infinity");
+    assert_eq!(consts::MAX.to_number(), f64::INFINITY);
+
+    assert!(consts::ZERO.is_zero());
+    assert!(consts::MAX.recip().is_zero());
+    assert!(!consts::ALMOST_ZERO.is_zero());
+    assert_eq!(consts::ZERO.classify(), FpCategory::Zero);
+
+    assert!(Decimal::new(1e300).is_finite());
+    assert!(from_mantissa_exponent(1.0, 1e300).is_finite());
+    assert!(Decimal::new(-42.0).is_normal());
+    assert_eq!(
+        from_mantissa_exponent_no_normalize(42.0, 0.0).classify(),
+        FpCategory::Subnormal
+    );
+
+    assert!(Decimal::new(42.0).is_integer());
+    assert!(Decimal::new(-1e20).is_integer());
+    assert!(consts::ZERO.is_integer());
+    assert!(!Decimal::new(4.2).is_integer());
+    assert!(!Decimal::new(0.5).is_integer());
+    assert!(!consts::MAX.is_integer());
+    assert!(!consts::NAN.is_integer());
+}
+
+#[test]
+fn parts() {
+    let value = Decimal::new(-1234.5);
+    assert_eq!(value.mantissa(), -1.2345);
+    assert_eq!(value.exponent(), 3.0);
+
+    assert_eq!(
+        Decimal::try_from_parts(12.5, 3.0),
+        Ok(Decimal::new(12500.0))
+    );
+    assert_eq!(Decimal::try_from_parts(0.0, 1e300), Ok(consts::ZERO));
+    assert!(Decimal::try_from_parts(1.0, 2.5)
+        .unwrap()
+        .eq_tolerance(&Decimal::new(10.0_f64.powf(2.5)), &Decimal::new(1e-12)));
+    assert_eq!(Decimal::try_from_parts(1.0, 2.5).unwrap().exponent(), 2.0);
+    assert_eq!(
+        Decimal::try_from_parts(f64::NAN, 1.0),
+        Err(TryFromPartsError::NonFiniteMantissa)
+    );
+    assert_eq!(
+        Decimal::try_from_parts(1.0, f64::INFINITY),
+        Err(TryFromPartsError::NonFiniteExponent)
+    );
+    assert_eq!(
+        Decimal::try_from_parts(1.0, 1.795e308),
+        Err(TryFromPartsError::ExponentOutOfRange)
+    );
+    assert_eq!(
+        Decimal::try_from_parts(1.0, consts::EXP_LIMIT),
+        Ok(consts::MAX)
+    );
+    // like in from_mantissa_exponent, too small values are zero rather than errors
+    assert_eq!(Decimal::try_from_parts(1.0, -1.795e308), Ok(consts::ZERO));
+    assert_eq!(
+        Decimal::try_from_parts(-5.0, consts::NEG_EXP_LIMIT),
+        Ok(consts::ZERO)
+    );
+    assert!(from_mantissa_exponent(1.0, -1.795e308).is_zero());
+
+    for value in [
+        consts::NAN,
+        consts::ZERO,
+        consts::MAX,
+        consts::MIN,
+        consts::INFINITY,
+        consts::ALMOST_ZERO,
+        Decimal::new(-42.0),
+        Decimal::new(1e300) * Decimal::new(1e300),
+    ] {
+        assert!(value.is_normalized(), "{value:?}");
+        assert!(Decimal::from_bits(&value.to_bits()).is_normalized());
+    }
+    assert!(!from_mantissa_exponent_no_normalize(42.0, 0.0).is_normalized());
+    assert!(!from_mantissa_exponent_no_normalize(4.2, 0.5).is_normalized());
+    assert!(!from_mantissa_exponent_no_normalize(0.0, 5.0).is_normalized());
+    assert!(!from_mantissa_exponent_no_normalize(f64::NAN, 0.0).is_normalized());
+    assert!(!Decimal::from_bits(&[u64::MAX, 0]).is_normalized());
+}
+
+#[test]
+fn pow_fast_path() {
+    // the fast path used to keep the mantissa unnormalized (2^10 as 1024e0)
+    // and fractional exponents as they were (10^2.5 as 1e2.5)
+    let value = Decimal::new(2.0).powi(10);
+    assert_eq!((value.mantissa(), value.exponent()), (1.024, 3.0));
+    assert_eq!(value, Decimal::new(1024.0));
+    assert_eq!(Decimal::new(-3.0).powi(3), Decimal::new(-27.0));
+    assert_eq!(Decimal::new(5.0).powi(-2), Decimal::new(0.04));
+
+    let value = Decimal::new(10.0).powf(2.5);
+    assert!(value.is_normalized());
+    assert_eq!(value.exponent(), 2.0);
+    assert!(value.eq_tolerance(&Decimal::new(10.0_f64.powf(2.5)), &Decimal::new(1e-12)));
+    assert!(Decimal::new(100.0).powf(0.25) > Decimal::new(3.16));
+    assert!(Decimal::new(100.0).powf(0.25) < Decimal::new(3.17));
+
+    // exponents beyond f64's range saturate instead of recursing
+    let huge = from_mantissa_exponent(4.3, 6.8e307);
+    assert_eq!(huge.powf(3.0), consts::MAX);
+    assert_eq!((-huge).powf(3.0), consts::MIN);
+    assert!(huge.powf(-3.0).is_zero());
+    assert!(Decimal::new(2.0).pow(&consts::NAN).is_nan());
+}
+
+#[test]
+fn gamma_and_combinatorics() {
+    assert_eq!(Decimal::new(0.0).factorial(), Decimal::new(1.0));
+    assert_eq!(Decimal::new(5.0).factorial(), Decimal::new(120.0));
+    assert_eq!(
+        Decimal::new(20.0).factorial(),
+        Decimal::new(2432902008176640000.0)
+    );
+    assert_eq!(
+        Decimal::new(170.0).factorial(),
+        Decimal::new(7.257415615307999e306)
+    );
+    assert_eq!(Decimal::new(6.0).gamma(), Decimal::new(120.0));
+    assert!(Decimal::new(0.0).gamma().is_nan());
+    assert!(Decimal::new(-3.0).gamma().is_nan());
+
+    let sqrt_pi = std::f64::consts::PI.sqrt();
+    let tolerance = Decimal::new(1e-13);
+    assert!(Decimal::new(0.5)
+        .gamma()
+        .eq_tolerance(&Decimal::new(sqrt_pi), &tolerance));
+    assert!(Decimal::new(-0.5)
+        .gamma()
+        .eq_tolerance(&Decimal::new(-2.0 * sqrt_pi), &tolerance));
+    assert!(Decimal::new(4.5)
+        .factorial()
+        .eq_tolerance(&Decimal::new(52.34277778455352), &tolerance));
+    // 199!, 1000!; beyond f64 the logarithm limits the precision
+    let tolerance_beyond = Decimal::new(1e-11);
+    assert!(Decimal::new(200.0).gamma().eq_tolerance(
+        &from_mantissa_exponent(3.943289336823952, 372.0),
+        &tolerance_beyond
+    ));
+    assert!(Decimal::new(1000.0).factorial().eq_tolerance(
+        &from_mantissa_exponent(4.023872600770938, 2567.0),
+        &tolerance_beyond
+    ));
+    assert!(Decimal::new(-200.5).gamma().is_finite());
+
+    assert!((Decimal::new(200.0).ln_gamma().to_number() - 857.9336698258575).abs() < 1e-10);
+    // ln(Γ(1e400)) = 1e400 * (ln(1e400) - 1) - ...
+    let huge = from_mantissa_exponent(1.0, 400.0);
+    assert!(huge.ln_gamma().eq_tolerance(
+        &(huge * (400.0 * std::f64::consts::LN_10 - 1.0)),
+        &tolerance
+    ));
+    assert!(huge.factorial().is_infinite());
+    assert!(Decimal::new(1e300).factorial().is_finite());
+
+    let binomial_of = |n: f64, k: f64| binomial(&Decimal::new(n), &Decimal::new(k));
+    let permutations_of = |n: f64, k: f64| permutations(&Decimal::new(n), &Decimal::new(k));
+    assert_eq!(binomial_of(10.0, 3.0), Decimal::new(120.0));
+    assert_eq!(binomial_of(52.0, 5.0), Decimal::new(2598960.0));
+    assert_eq!(binomial_of(50.0, 25.0), Decimal::new(126410606437752.0));
+    assert!(binomial_of(60.0, 30.0).eq_tolerance(&Decimal::new(1.1826458156486142e17), &tolerance));
+    assert_eq!(binomial_of(5.0, 0.0), Decimal::new(1.0));
+    assert_eq!(binomial_of(5.0, 6.0), Decimal::new(0.0));
+    assert!(binomial_of(-5.0, 2.0).is_nan());
+    assert!(binomial_of(1000.0, 500.0).eq_tolerance(
+        &from_mantissa_exponent(2.7028824094543655, 299.0),
+        &tolerance_beyond
+    ));
+    assert!(binomial_of(2.5, 1.5).eq_tolerance(&Decimal::new(2.5), &tolerance));
+
+    assert_eq!(permutations_of(10.0, 3.0), Decimal::new(720.0));
+    assert_eq!(permutations_of(10.0, 10.0), Decimal::new(3628800.0));
+    assert_eq!(permutations_of(3.0, 4.0), Decimal::new(0.0));
+    assert!(permutations_of(1e20, 2.0).eq_tolerance(&Decimal::new(1e40), &tolerance));
+    // 1e20! / (1e20 - 1000)! is about 1e20^1000
+    assert!(permutations_of(1e20, 1000.0)
+        .eq_tolerance(&from_mantissa_exponent(1.0, 20000.0), &Decimal::new(1e-9)));
+}
+
+#[test]
+fn lambert_w() {
+    let tolerance = Decimal::new(1e-13);
+    let w = |x: f64| Decimal::new(x).lambert_w();
+    assert!(w(1.0).eq_tolerance(&Decimal::new(0.5671432904097838), &tolerance));
+    assert!(w(std::f64::consts::E).eq_tolerance(&Decimal::new(1.0), &tolerance));
+    assert!(w(-1.0 / std::f64::consts::E).eq_tolerance(&Decimal::new(-1.0), &Decimal::new(1e-7)));
+    assert!(w(-0.2).eq_tolerance(&Decimal::new(-0.2591711018190737), &tolerance));
+    assert_eq!(w(0.0), Decimal::new(0.0));
+    assert_eq!(w(1e-20), Decimal::new(1e-20));
+    assert!(w(-0.5).is_nan());
+    assert!(Decimal::infinity().lambert_w().is_infinite());
+
+    // w + ln(w) = ln(x) beyond f64
+    for exponent in [300.0, 1000.0, 1e10] {
+        let w = from_mantissa_exponent(1.0, exponent)
+            .lambert_w()
+            .to_number();
+        let ln = exponent * std::f64::consts::LN_10;
+        assert!(((w + w.ln()) - ln).abs() < 1e-13 * ln);
+    }
+    let ln = Decimal::new(1e300) * std::f64::consts::LN_10;
+    assert!(from_mantissa_exponent(1.0, 1e300)
+        .lambert_w()
+        .eq_tolerance(&(ln - ln.ln()), &tolerance));
+
+    let w_m1 = |x: f64| Decimal::new(x).lambert_w_m1();
+    assert!(w_m1(-0.1).eq_tolerance(&Decimal::new(-3.577152063957297), &tolerance));
+    assert!(w_m1(-0.3).eq_tolerance(&Decimal::new(-1.7813370234216276), &tolerance));
+    assert!(w_m1(0.1).is_nan());
+    assert!(w_m1(0.0).is_nan());
+    assert!(w_m1(-0.5).is_nan());
+    // w + ln(-w) = ln(-x)
+    let w = from_mantissa_exponent(-1.0, -400.0)
+        .lambert_w_m1()
+        .to_number();
+    let ln = -400.0 * std::f64::consts::LN_10;
+    assert!(((w + (-w).ln()) - ln).abs() < 1e-13 * ln.abs());
+
+    let afford = |resources: f64| {
+        afford_linear_exponential(
+            &Decimal::new(resources),
+            &Decimal::new(1.0),
+            &Decimal::new(2.0),
+        )
+    };
+    assert_eq!(afford(24.0), Decimal::new(3.0));
+    assert_eq!(afford(23.9), Decimal::new(2.0));
+    assert_eq!(afford(1.0), Decimal::new(0.0));
+    let level = afford(1e100).to_number();
+    assert!(level * 2.0_f64.powf(level) <= 1e100);
+    assert!((level + 1.0) * 2.0_f64.powf(level + 1.0) > 1e100);
+    let afford_with = |price_start: f64, price_ratio: f64| {
+        afford_linear_exponential(
+            &Decimal::new(100.0),
+            &Decimal::new(price_start),
+            &Decimal::new(price_ratio),
+        )
+    };
+    assert_eq!(afford_with(3.0, 1.0), Decimal::new(33.0));
+    assert_eq!(afford_with(1.0, 0.5), consts::INFINITY);
+    assert_eq!(afford_with(0.0, 2.0), consts::INFINITY);
+    // free purchases have no limit either
+    let (hundred, zero, two) = (Decimal::new(100.0), consts::ZERO, consts::TWO);
+    assert_eq!(
+        afford_geometric_series(&hundred, &zero, &two, &zero),
+        consts::INFINITY
+    );
+    assert_eq!(
+        afford_arithmetic_series(&hundred, &zero, &zero, &two),
+        consts::INFINITY
+    );
+
+    // (1 + t) * 2^t
+    let time = |target: &Decimal| {
+        time_to_reach_linear_exponential(
+            target,
+            &Decimal::new(1.0),
+            &Decimal::new(1.0),
+            &Decimal::new(2.0),
+        )
+    };
+    assert!(time(&Decimal::new(11264.0)).eq_tolerance(&Decimal::new(10.0), &tolerance));
+    assert_eq!(time(&Decimal::new(0.5)), Decimal::new(0.0));
+    // (1 + t) * 2^t = 1e1000 at t ≈ (1000 * ln(10) - ln(t)) / ln(2)
+    let t = time(&from_mantissa_exponent(1.0, 1000.0)).to_number();
+    assert!(((1.0 + t).log10() + t * 2.0_f64.log10() - 1000.0).abs() < 1e-10);
+    assert!(time_to_reach_linear_exponential(
+        &Decimal::new(50.0),
+        &Decimal::new(10.0),
+        &Decimal::new(4.0),
+        &Decimal::new(1.0)
+    )
+    .eq_tolerance(&Decimal::new(10.0), &tolerance));
+
+    // targets which are never reached
+    let time_with = |start: f64, slope: f64, growth: f64| {
+        time_to_reach_linear_exponential(
+            &Decimal::new(50.0),
+            &Decimal::new(start),
+            &Decimal::new(slope),
+            &Decimal::new(growth),
+        )
+    };
+    assert_eq!(time_with(10.0, 0.0, 1.0), consts::INFINITY);
+    assert_eq!(time_with(10.0, 4.0, 0.5), consts::INFINITY);
+    assert_eq!(time_with(10.0, -4.0, 2.0), consts::INFINITY);
+    assert_eq!(time_with(0.0, 0.0, 2.0), consts::INFINITY);
+    assert!(time_with(10.0, 0.0, 5.0).eq_tolerance(&Decimal::new(1.0), &tolerance));
+}
+
+#[test]
+fn decimal_logarithms() {
+    let tolerance = Decimal::new(1e-14);
+    assert_eq!(Decimal::new(1000.0).log10_decimal(), Decimal::new(3.0));
+    assert!(Decimal::new(8.0)
+        .log_decimal(&Decimal::new(2.0))
+        .eq_tolerance(&Decimal::new(3.0), &tolerance));
+    assert!(from_mantissa_exponent(1.0, 1000.0)
+        .log_decimal(&Decimal::new(1e100))
+        .eq_tolerance(&Decimal::new(10.0), &tolerance));
+    assert!(Decimal::new(0.5)
+        .ln_decimal()
+        .eq_tolerance(&Decimal::new(-std::f64::consts::LN_2), &tolerance));
+
+    // beyond the range of the f64 variants
+    assert!(consts::ALMOST_ZERO.ln().is_infinite());
+    assert!(consts::ALMOST_ZERO.ln_decimal().is_finite());
+    assert!(consts::ALMOST_ZERO.ln_decimal().eq_tolerance(
+        &(Decimal::new(consts::ALMOST_ZERO.exponent()) * std::f64::consts::LN_10),
+        &tolerance
+    ));
+    let huge = from_mantissa_exponent(1.0, 1e308);
+    assert!(huge.ln_decimal().eq_tolerance(
+        &from_mantissa_exponent(std::f64::consts::LN_10, 308.0),
+        &tolerance
+    ));
+    // log base 1.0001 of 1e1e308 is about 2.3e312
+    assert!(huge.log_decimal(&Decimal::new(1.0001)).eq_tolerance(
+        &(Decimal::new(1e308) * (std::f64::consts::LN_10 / 1.0001_f64.ln())),
+        &Decimal::new(1e-12)
+    ));
+
+    assert!(Decimal::new(8.0).log(0.5).is_nan());
+    assert!(Decimal::new(8.0).log(1.0).is_nan());
+    assert!(Decimal::new(-8.0).log(2.0).is_nan());
+    assert!(Decimal::new(0.0).log(2.0).is_nan());
+    assert!(Decimal::new(8.0).log_decimal(&Decimal::new(1.0)).is_nan());
+    assert!(Decimal::new(8.0).log_decimal(&Decimal::new(0.5)).is_nan());
+    assert!(Decimal::new(-8.0).log_decimal(&Decimal::new(2.0)).is_nan());
+    assert!(Decimal::new(0.0).ln_decimal().is_nan());
+    assert!(Decimal::new(-1.0).log10_decimal().is_nan());
+}
+
+#[test]
+fn trigonometry() {
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    let tolerance = Decimal::new(1e-15);
+    assert_eq!(Decimal::new(0.0).sin(), Decimal::new(0.0));
+    assert_eq!(Decimal::new(0.0).cos(), Decimal::new(1.0));
+    assert!(Decimal::new(FRAC_PI_2)
+        .sin()
+        .eq_tolerance(&Decimal::new(1.0), &tolerance));
+    assert!(Decimal::new(PI)
+        .cos()
+        .eq_tolerance(&Decimal::new(-1.0), &tolerance));
+    assert!(Decimal::new(FRAC_PI_4)
+        .tan()
+        .eq_tolerance(&Decimal::new(1.0), &tolerance));
+    // without std, libm may differ from std in the last bit
+    assert!(Decimal::new(1e6)
+        .sin()
+        .eq_tolerance(&Decimal::new(1e6_f64.sin()), &tolerance));
+    assert!(Decimal::new(-1e15)
+        .cos()
+        .eq_tolerance(&Decimal::new(1e15_f64.cos()), &tolerance));
+    // no meaningful phase left
+    assert!(Decimal::new(1e17).sin().is_nan());
+    assert!(from_mantissa_exponent(1.0, 400.0).cos().is_nan());
+    // sin(x) = x for tiny x
+    let tiny = from_mantissa_exponent(2.0, -400.0);
+    assert_eq!(tiny.sin(), tiny);
+    assert_eq!(tiny.tan(), tiny);
+    assert_eq!(tiny.cos(), Decimal::new(1.0));
+
+    assert!(Decimal::new(1.0)
+        .asin()
+        .eq_tolerance(&Decimal::new(FRAC_PI_2), &tolerance));
+    assert!(Decimal::new(-1.0)
+        .acos()
+        .eq_tolerance(&Decimal::new(PI), &tolerance));
+    assert!(Decimal::new(2.0).asin().is_nan());
+    assert!(Decimal::new(-1.5).acos().is_nan());
+    assert!(from_mantissa_exponent(1.0, 400.0)
+        .atan()
+        .eq_tolerance(&Decimal::new(FRAC_PI_2), &tolerance));
+    assert_eq!(tiny.atan(), tiny);
+    assert_eq!(tiny.asin(), tiny);
+
+    let atan2 = |y: &Decimal, x: &Decimal| y.atan2(x);
+    let one = Decimal::new(1.0);
+    assert!(atan2(&one, &one).eq_tolerance(&Decimal::new(FRAC_PI_4), &tolerance));
+    assert!(atan2(&one, &-one).eq_tolerance(&Decimal::new(3.0 * FRAC_PI_4), &tolerance));
+    assert!(atan2(&-one, &Decimal::new(0.0)).eq_tolerance(&Decimal::new(-FRAC_PI_2), &tolerance));
+    // both beyond f64
+    let huge = from_mantissa_exponent(1.0, 400.0);
+    assert!(atan2(&huge, &-huge).eq_tolerance(&Decimal::new(3.0 * FRAC_PI_4), &tolerance));
+    assert!(atan2(&tiny, &-tiny).eq_tolerance(&Decimal::new(3.0 * FRAC_PI_4), &tolerance));
+    assert_eq!(atan2(&tiny, &one), tiny);
+
+    assert!(Decimal::new(1.0)
+        .asinh()
+        .eq_tolerance(&Decimal::new(1.0_f64.asinh()), &tolerance));
+    assert!(Decimal::new(2.0)
+        .acosh()
+        .eq_tolerance(&Decimal::new(2.0_f64.acosh()), &tolerance));
+    assert!(Decimal::new(0.5)
+        .atanh()
+        .eq_tolerance(&Decimal::new(0.5_f64.atanh()), &tolerance));
+    assert!(Decimal::new(0.5).acosh().is_nan());
+    assert!(Decimal::new(1.0).atanh().is_nan());
+    // asinh(x) = ln(2x) for huge x
+    assert!((-huge).asinh().eq_tolerance(
+        &Decimal::new(-(2.0_f64.ln() + 400.0 * std::f64::consts::LN_10)),
+        &tolerance
+    ));
+    let huger = from_mantissa_exponent(1.0, 1e308);
+    assert!(huger.acosh().eq_tolerance(&huger.ln_decimal(), &tolerance));
+}
+
+#[test]
+fn softcaps() {
+    use break_infinity_extended::softcap::*;
+
+    let tolerance = Decimal::new(1e-12);
+    let start = Decimal::new(1e10);
+    let caps: Vec<Box<dyn Softcap>> = vec![
+        Box::new(PowerSoftcap { start, power: 0.5 }),
+        Box::new(LogarithmicSoftcap { start }),
+        Box::new(DilationSoftcap { start, power: 0.75 }),
+        Box::new(DivisionSoftcap {
+            start,
+            divisor: Decimal::new(4.0),
+        }),
+    ];
+    for cap in &caps {
+        assert_eq!(cap.apply(&Decimal::new(123.0)), Decimal::new(123.0));
+        assert_eq!(cap.invert(&Decimal::new(123.0)), Decimal::new(123.0));
+        assert!(cap.apply(&start).eq_tolerance(&start, &tolerance));
+        for value in [
+            Decimal::new(2e10),
+            Decimal::new(1e50),
+            from_mantissa_exponent(3.0, 1000.0),
+        ] {
+            let capped = cap.apply(&value);
+            assert!(capped < value);
+            assert!(cap.invert(&capped).eq_tolerance(&value, &tolerance));
+        }
+    }
+
+    assert_eq!(
+        PowerSoftcap { start, power: 0.5 }.apply(&Decimal::new(1e20)),
+        Decimal::new(1e15)
+    );
+    assert!(LogarithmicSoftcap { start }
+        .apply(&Decimal::new(1e11))
+        .eq_tolerance(&(start * (1.0 + std::f64::consts::LN_10)), &tolerance));
+    // 10^(10 * (20 / 10)^0.5)
+    assert!(DilationSoftcap { start, power: 0.5 }
+        .apply(&Decimal::new(1e20))
+        .eq_tolerance(
+            &Decimal::new(10.0_f64.powf(10.0 * 2.0_f64.sqrt())),
+            &tolerance
+        ));
+    assert_eq!(
+        DivisionSoftcap {
+            start,
+            divisor: Decimal::new(4.0)
+        }
+        .apply(&Decimal::new(5e10)),
+        Decimal::new(2e10)
+    );
+
+    let hardcap = Hardcap { limit: start };
+    assert_eq!(hardcap.apply(&Decimal::new(1e20)), start);
+    assert_eq!(hardcap.invert(&start), start);
+    assert!(hardcap.invert(&Decimal::new(1e20)).is_nan());
+    assert!(hardcap.apply(&Decimal::nan()).is_nan());
+
+    let stacked = StackedSoftcap::new()
+        .then(PowerSoftcap { start, power: 0.5 })
+        .then(DilationSoftcap {
+            start: Decimal::new(1e100),
+            power: 0.5,
+        })
+        .then(Hardcap {
+            limit: from_mantissa_exponent(1.0, 1e6),
+        });
+    assert_eq!(stacked.apply(&Decimal::new(1e5)), Decimal::new(1e5));
+    let value = from_mantissa_exponent(1.0, 1e4);
+    let capped = stacked.apply(&value);
+    // 1e4 orders of magnitude become 5005, then 100 * sqrt(50.05)
+    assert!((capped.log10() - 100.0 * 50.05_f64.sqrt()).abs() < 1e-9);
+    assert!(stacked.invert(&capped).eq_tolerance(&value, &tolerance));
+    assert_eq!(
+        stacked.apply(&from_mantissa_exponent(1.0, 1e300)),
+        from_mantissa_exponent(1.0, 1e6)
+    );
+    let extended = stacked.clone().then(Hardcap { limit: start });
+    assert_eq!(extended.apply(&value), start);
+    assert_eq!(stacked.apply(&value), capped);
+    assert!(format!("{extended:?}").starts_with("StackedSoftcap { caps: [PowerSoftcap {"));
+    assert!(StackedSoftcap::new()
+        .invert(&Decimal::new(5.0))
+        .eq(&Decimal::new(5.0)));
+
+    fn shareable<T: Send + Sync>(_: &T) {}
+    shareable(&stacked);
+    let shared = std::thread::spawn(move || stacked.apply(&value))
+        .join()
+        .unwrap();
+    assert_eq!(shared, capped);
+}
+
+#[test]
+fn time_to_target() {
+    let tolerance = Decimal::new(1e-14);
+
+    assert_eq!(
+        time_to_reach_linear(
+            &Decimal::new(100.0),
+            &Decimal::new(10.0),
+            &Decimal::new(3.0)
+        ),
+        Decimal::new(30.0)
+    );
+    assert_eq!(
+        time_to_reach_linear(&Decimal::new(5.0), &Decimal::new(10.0), &Decimal::new(0.0)),
+        Decimal::new(0.0)
+    );
+    assert!(time_to_reach_linear(
+        &Decimal::new(100.0),
+        &Decimal::new(10.0),
+        &Decimal::new(0.0)
+    )
+    .is_infinite());
+
+    // 10 * 1.5^t
+    let time = time_to_reach_exponential(
+        &from_mantissa_exponent(1.0, 1000.0),
+        &Decimal::new(10.0),
+        &Decimal::new(1.5),
+    );
+    assert!(time.eq_tolerance(&Decimal::new(999.0 / 1.5_f64.log10()), &tolerance));
+    assert!(time_to_reach_exponential(
+        &Decimal::new(100.0),
+        &Decimal::new(10.0),
+        &Decimal::new(1.0)
+    )
+    .is_infinite());
+    // beyond the exponents an f64 can hold
+    let time = time_to_reach_exponential(
+        &from_mantissa_exponent(1.0, 1e308),
+        &Decimal::new(1.0),
+        &Decimal::new(10.0),
+    );
+    assert!(time.eq_tolerance(&Decimal::new(1e308), &tolerance));
+
+    // 5 + 3t + 2t^2 = 95 at t = 6
+    let quadratic = [Decimal::new(5.0), Decimal::new(3.0), Decimal::new(2.0)];
+    assert!(time_to_reach_polynomial(&Decimal::new(95.0), &quadratic)
+        .eq_tolerance(&Decimal::new(6.0), &tolerance));
+    // trailing zeros don't make it a cubic
+    let padded = [
+        Decimal::new(5.0),
+        Decimal::new(3.0),
+        Decimal::new(2.0),
+        Decimal::new(0.0),
+    ];
+    assert!(time_to_reach_polynomial(&Decimal::new(95.0), &padded)
+        .eq_tolerance(&Decimal::new(6.0), &tolerance));
+    assert!(time_to_reach_polynomial(&Decimal::new(95.0), &[Decimal::new(5.0)]).is_infinite());
+    assert_eq!(
+        time_to_reach_polynomial(&Decimal::new(1.0), &[Decimal::new(5.0)]),
+        Decimal::new(0.0)
+    );
+
+    // the currency of a chain of three tiers with a single top tier: t^3 / 6
+    let chain = [
+        Decimal::new(0.0),
+        Decimal::new(0.0),
+        Decimal::new(0.0),
+        Decimal::new(1.0 / 6.0),
+    ];
+    let time = time_to_reach_polynomial(&Decimal::new(1e30), &chain);
+    assert!(time.eq_tolerance(&Decimal::new(6e30_f64.cbrt()), &tolerance));
+    let time = time_to_reach_polynomial(
+        &(from_mantissa_exponent(1.0, 3000.0) / Decimal::new(6.0)),
+        &chain,
+    );
+    assert!(time.eq_tolerance(&from_mantissa_exponent(1.0, 1000.0), &tolerance));
+
+    // bisection never stops short of the target
+    let value = |t: &Decimal| t.sqr() * Decimal::new(3.0) + t.sqrt();
+    for target in [1e-20, 0.1, 2.0, 1e10, 1e200] {
+        let target = Decimal::new(target);
+        let time = time_to_reach(&target, value);
+        assert!(value(&time) >= target);
+        assert!(value(&(time * Decimal::new(1.0 - 1e-12))) < target);
+    }
+    assert_eq!(time_to_reach(&Decimal::new(-1.0), value), Decimal::new(0.0));
+    assert!(time_to_reach(&Decimal::new(10.0), |t| t.min(&Decimal::new(5.0))).is_infinite());
+}
+
+#[test]
+fn dec_macro() {
+    const COSTS: [Decimal; 3] = [dec!(10), dec!(1.5e1000), dec!("2.5e1e5")];
+    assert_eq!(COSTS[0], Decimal::new(10.0));
+    assert_eq!(COSTS[1], from_mantissa_exponent(1.5, 1000.0));
+    assert_eq!(COSTS[2], from_mantissa_exponent(2.5, 1e5));
+
+    // the same values as parsing at runtime
+    let pairs = [
+        (dec!(0), "0"),
+        (dec!(1), "1"),
+        (dec!(-1), "-1"),
+        (dec!(123.456), "123.456"),
+        (dec!(-2.5e-3), "-2.5e-3"),
+        (dec!(1_000_000), "1000000"),
+        (dec!(12345678901234567890), "12345678901234567890"),
+        (dec!(1e308), "1e308"),
+        (dec!(3e-320), "3e-320"),
+        (dec!("7.5e1.5"), "7.5e1.5"),
+        (dec!("  -4.2E+50 "), "-4.2E+50"),
+        (dec!("1e1.79e308"), "1e1.79e308"),
+        (dec!("-Infinity"), "-Infinity"),
+        (dec!("inf"), "inf"),
+        (dec!(0.5), ".5"),
+        (dec!("5."), "5."),
+        (dec!(-0.0), "-0"),
+        (dec!(0e999), "0e999"),
+        (dec!(9999999999999999), "9999999999999999"),
+        (dec!(1e-400), "1e-400"),
+        (dec!(4.9e-324), "4.9e-324"),
+        (dec!(1.7976931348623157e308), "1.7976931348623157e308"),
+        (dec!(1e400), "1e400"),
+        (dec!("1e1e5"), "1e1e5"),
+        (dec!("-3.3e-1.79e308"), "-3.3e-1.79e308"),
+        (
+            dec!("123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890.5e10"),
+            "123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890.5e10",
+        ),
+    ];
+    for (literal, string) in pairs {
+        let parsed: Decimal = string.parse().unwrap();
+        assert_eq!(literal.mantissa(), parsed.mantissa(), "{}", string);
+        assert_eq!(literal.exponent(), parsed.exponent(), "{}", string);
+    }
+    assert!(dec!("NaN").is_nan());
+}
+
+#[test]
+fn const_fns() {
+    const BASE: Decimal = Decimal::from_parts_normalized(1500.0, 997.0);
+    const NEGATED: Decimal = BASE.negate();
+    const ABS: Decimal = NEGATED.abs();
+    const BITS: [u64; 2] = BASE.to_bits();
+    const ROUND_TRIP: Decimal = Decimal::from_bits(&BITS);
+    const LARGER: Decimal = BASE.max(&NEGATED);
+    const IS_LESS: bool = NEGATED.lt(&BASE);
+    const ORDERING: Option<std::cmp::Ordering> = BASE.compare(&ABS);
+
+    assert_eq!(BASE, from_mantissa_exponent(1.5, 1000.0));
+    assert_eq!(NEGATED, -BASE);
+    assert_eq!(ABS, BASE);
+    assert_eq!(ROUND_TRIP, BASE);
+    assert_eq!(LARGER, BASE);
+    assert_eq!(IS_LESS, NEGATED < BASE);
+    assert_eq!(ORDERING, Some(std::cmp::Ordering::Equal));
+    assert_eq!(Decimal::nan().compare(&BASE), None);
+    assert!(!Decimal::nan().lte(&Decimal::nan()));
+
+    // the same as normalizing at runtime, at least close to the normalized range
+    for mantissa in [
+        1.0, 9.5, 10.0, 0.05, 0.1, 0.3, 12345.678, 99.99, 5e21, 9.99e21, 3.3e-5, 1e-22, 7e-15,
+    ] {
+        for sign in [1.0, -1.0] {
+            let mantissa = mantissa * sign;
+            let normalized = Decimal::from_parts_normalized(mantissa, 10.0);
+            let expected = from_mantissa_exponent(mantissa, 10.0);
+            assert_eq!(normalized.to_bits(), expected.to_bits(), "{}", mantissa);
+        }
+    }
+    for (mantissa, string) in [
+        (1e23, "1e23"),
+        (4.2e100, "4.2e100"),
+        (1.7e308, "1.7e308"),
+        (2.5e-23, "2.5e-23"),
+        (1e-200, "1e-200"),
+    ] {
+        let normalized = Decimal::from_parts_normalized(mantissa, 0.0);
+        assert!(normalized.is_normalized(), "{}", mantissa);
+        assert!(normalized.eq_tolerance(&string.parse().unwrap(), &Decimal::new(1e-14)));
+    }
+    // even subnormal mantissas, with what precision they have
+    let subnormal = Decimal::from_parts_normalized(3e-320, 0.0);
+    assert!(subnormal.is_normalized());
+    assert!(subnormal.eq_tolerance(&"3e-320".parse().unwrap(), &Decimal::new(1e-4)));
+    assert_eq!(Decimal::from_parts_normalized(0.0, 5.0), Decimal::zero());
+    assert!(Decimal::from_parts_normalized(f64::INFINITY, 0.0).is_nan());
+    assert!(Decimal::from_parts_normalized(1.0, f64::NAN).is_nan());
+}
+
+#[test]
+fn compact_encoding() {
+    let check = |value: Decimal, size: usize| {
+        let bytes = value.to_compact_bytes();
+        assert_eq!(bytes.len(), size, "{value:?}");
+        let (decoded, read) = Decimal::decode_compact(&bytes).unwrap();
+        assert_eq!(decoded.to_le_bytes(), value.to_le_bytes(), "{value:?}");
+        assert_eq!(read, size);
+        // every truncation is an error, not a panic
+        for end in 0..size {
+            assert_eq!(
+                Decimal::decode_compact(&bytes[..end]),
+                Err(DecodeDecimalError::UnexpectedEnd { offset: end })
+            );
+        }
+    };
+
+    for value in [
+        consts::ZERO,
+        consts::ONE,
+        consts::NAN,
+        consts::MAX,
+        consts::MIN,
+        consts::INFINITY,
+        consts::NEG_INFINITY,
+    ] {
+        check(value, 1);
+    }
+    check(Decimal::new(2.0), 6);
+    check(Decimal::new(-0.25), 6);
+    check(from_mantissa_exponent(1.5, 63.0), 6);
+    check(from_mantissa_exponent(1.5, -64.0), 6);
+    check(from_mantissa_exponent(1.5, 64.0), 7);
+    check(from_mantissa_exponent(3.3, 300.0), 11);
+    check(from_mantissa_exponent(3.3, -1e15), 17);
+    check(from_mantissa_exponent(3.3, 1e300), 17);
+    check(Decimal::from_bits(&[u64::MAX, 0]), 10);
+    check(from_mantissa_exponent_no_normalize(-0.0, 0.0), 6);
+    check(from_mantissa_exponent_no_normalize(4.2, 0.5), 17);
+    check(from_mantissa_exponent_no_normalize(4.2, -0.0), 17);
+
+    // a spread of normalized values of all signs and magnitudes
+    let mut seed: u64 = 42;
+    let mut values = Vec::new();
+    for exponent in [
+        0.0, 1.0, 63.0, 200.0, 5e4, 1e9, 9e15, 9.2e18, 1e19, 1e200, 1.7e308,
+    ] {
+        for _ in 0..50 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let mantissa = 1.0 + 9.0 * (seed >> 11) as f64 / (1u64 << 53) as f64;
+            for sign in [1.0, -1.0] {
+                let value = from_mantissa_exponent(sign * mantissa, sign * exponent);
+                assert!(value.is_normalized());
+                check(value, value.to_compact_bytes().len());
+                values.push(value);
+            }
+        }
+    }
+    values.extend([consts::ZERO, consts::NAN, Decimal::new(1.0)]);
+
+    let bytes = encode_slice(&values);
+    let (decoded, read) = decode_slice(&bytes).unwrap();
+    assert_eq!(read, bytes.len());
+    assert_eq!(decoded.len(), values.len());
+    for (decoded, value) in decoded.iter().zip(&values) {
+        assert_eq!(decoded.to_bits(), value.to_bits());
+    }
+    let bytes = encode_slice(&values[..20]);
+    for end in 0..bytes.len() {
+        assert!(matches!(
+            decode_slice(&bytes[..end]),
+            Err(DecodeDecimalError::UnexpectedEnd { .. })
+        ));
+    }
+    assert_eq!(decode_slice(&encode_slice(&[])), Ok((vec![], 1)));
+
+    // only what belongs to the values is read
+    let mut bytes = encode_slice(&[Decimal::new(7.0)]);
+    bytes.extend([1, 2, 3]);
+    assert_eq!(decode_slice(&bytes), Ok((vec![Decimal::new(7.0)], 7)));
+
+    assert_eq!(
+        Decimal::decode_compact(&[10]),
+        Err(DecodeDecimalError::InvalidTag { offset: 0, tag: 10 })
+    );
+    assert_eq!(
+        decode_slice(&[2, 1, 255]),
+        Err(DecodeDecimalError::InvalidTag {
+            offset: 2,
+            tag: 255
+        })
+    );
+    let overlong = [
+        7, 0, 0, 192, 63, 128, 128, 128, 128, 128, 128, 128, 128, 128, 2,
+    ];
+    assert_eq!(
+        Decimal::decode_compact(&overlong),
+        Err(DecodeDecimalError::InvalidVarint { offset: 5 })
+    );
+    // a count far beyond the input doesn't allocate for it
+    let huge_count = [255, 255, 255, 255, 255, 255, 255, 255, 255, 1, 0];
+    assert_eq!(
+        decode_slice(&huge_count),
+        Err(DecodeDecimalError::UnexpectedEnd { offset: 11 })
+    );
+    assert_eq!(
+        DecodeDecimalError::InvalidVarint { offset: 5 }.to_string(),
+        "invalid varint at byte 5"
+    );
+}
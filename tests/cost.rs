@@ -0,0 +1,306 @@
+use break_infinity_extended::*;
+
+fn d(value: f64) -> Decimal {
+    Decimal::new(value)
+}
+
+fn brute_force_total(model: &CostModel, owned: f64, count: u64) -> Decimal {
+    sum_compensated((0..count).map(|i| model.cost_of(&d(owned + i as f64))))
+}
+
+fn brute_force_affordable(model: &CostModel, owned: f64, budget: &Decimal) -> Decimal {
+    let mut count = 0;
+    while brute_force_total(model, owned, count + 1) <= *budget {
+        count += 1;
+    }
+    d(count as f64)
+}
+
+fn models() -> Vec<CostModel> {
+    vec![
+        CostModel::Linear {
+            base: d(10.0),
+            increment: d(3.0),
+        },
+        CostModel::Linear {
+            base: d(7.0),
+            increment: d(0.0),
+        },
+        CostModel::Exponential {
+            base: d(10.0),
+            ratio: d(1.15),
+        },
+        CostModel::Exponential {
+            base: d(5.0),
+            ratio: d(1.0),
+        },
+        CostModel::Polynomial {
+            base: d(2.0),
+            power: 2.5,
+        },
+        CostModel::Superexponential {
+            base: d(10.0),
+            ratio: d(1.5),
+            scaling: d(1.02),
+        },
+        CostModel::Piecewise(vec![
+            (
+                d(0.0),
+                CostModel::Exponential {
+                    base: d(10.0),
+                    ratio: d(2.0),
+                },
+            ),
+            (
+                d(20.0),
+                CostModel::Superexponential {
+                    base: d(10.0 * 2.0_f64.powi(20)),
+                    ratio: d(2.0),
+                    scaling: d(1.1),
+                },
+            ),
+        ]),
+    ]
+}
+
+#[test]
+fn costs() {
+    let linear = &models()[0];
+    assert_eq!(linear.cost_of(&d(0.0)), d(10.0));
+    assert_eq!(linear.cost_of(&d(4.0)), d(22.0));
+
+    let superexponential = CostModel::Superexponential {
+        base: d(1.0),
+        ratio: d(2.0),
+        scaling: d(10.0),
+    };
+    // 2^n * 10^(n * (n - 1) / 2)
+    assert_eq!(superexponential.cost_of(&d(0.0)), d(1.0));
+    assert_eq!(superexponential.cost_of(&d(1.0)), d(2.0));
+    assert_eq!(superexponential.cost_of(&d(3.0)), d(8e3));
+
+    let piecewise = &models()[6];
+    assert_eq!(piecewise.cost_of(&d(19.0)), d(10.0 * 2.0_f64.powi(19)));
+    assert_eq!(piecewise.cost_of(&d(20.0)), d(10.0 * 2.0_f64.powi(20)));
+    assert_eq!(piecewise.cost_of(&d(21.0)), d(10.0 * 2.0_f64.powi(21)));
+
+    // without any models there are no costs to work with
+    let empty = CostModel::Piecewise(vec![]);
+    assert!(empty.cost_of(&d(0.0)).is_nan());
+    assert!(empty.total_cost(&d(0.0), &d(5.0)).is_nan());
+    assert!(empty.max_affordable(&d(0.0), &d(100.0)).is_nan());
+}
+
+#[test]
+fn total_costs_match_brute_force() {
+    let tolerance = d(1e-12);
+    for model in models() {
+        for owned in [0.0, 5.0, 17.0] {
+            assert_eq!(model.total_cost(&d(owned), &d(0.0)), d(0.0));
+            for count in 1..40 {
+                assert!(
+                    model
+                        .total_cost(&d(owned), &d(count as f64))
+                        .eq_tolerance(&brute_force_total(&model, owned, count), &tolerance),
+                    "{:?} owned {} count {}",
+                    model,
+                    owned,
+                    count
+                );
+            }
+        }
+    }
+
+    // beyond the summed terms: the sum of k^2 for k up to n is n * (n + 1) * (2n + 1) / 6
+    let squares = CostModel::Polynomial {
+        base: d(1.0),
+        power: 2.0,
+    };
+    for n in [2_000.0, 1e6, 1e20, 1e100] {
+        let exact = d(n) * d(n + 1.0) * d(2.0 * n + 1.0) / d(6.0);
+        assert!(squares
+            .total_cost(&d(0.0), &d(n))
+            .eq_tolerance(&exact, &tolerance));
+    }
+    // the sum of k^3 is (n * (n + 1) / 2)^2, where the formula is exact as well
+    let cubes = CostModel::Polynomial {
+        base: d(1.0),
+        power: 3.0,
+    };
+    for n in [1_001.0, 2_000.0, 1e6] {
+        let exact = (d(n) * d(n + 1.0) / d(2.0)).sqr();
+        assert!(cubes
+            .total_cost(&d(0.0), &d(n))
+            .eq_tolerance(&exact, &tolerance));
+    }
+    // a few squares on top of many
+    let owned = 1e12;
+    let exact = brute_force_total(&squares, owned, 5_000);
+    assert!(squares
+        .total_cost(&d(owned), &d(5_000.0))
+        .eq_tolerance(&exact, &tolerance));
+
+    // the last purchase dominates
+    let superexponential = CostModel::Superexponential {
+        base: d(1.0),
+        ratio: d(1.0),
+        scaling: d(1.001),
+    };
+    let total = superexponential.total_cost(&d(0.0), &d(1e6));
+    let last = superexponential.cost_of(&d(1e6 - 1.0));
+    assert!(total >= last && total < last * d(1.01));
+
+    // without scaling, far more purchases than are summed up term by term still count
+    let constant = CostModel::Superexponential {
+        base: d(3.0),
+        ratio: d(1.0),
+        scaling: d(1.0),
+    };
+    assert_eq!(constant.total_cost(&d(0.0), &d(1e5)), d(3e5));
+    assert_eq!(constant.max_affordable(&d(0.0), &d(3e5)), d(1e5));
+    let exponential = CostModel::Exponential {
+        base: d(3.0),
+        ratio: d(1.0001),
+    };
+    let unscaled = CostModel::Superexponential {
+        base: d(3.0),
+        ratio: d(1.0001),
+        scaling: d(1.0),
+    };
+    assert_eq!(
+        unscaled.total_cost(&d(10.0), &d(5e4)),
+        exponential.total_cost(&d(10.0), &d(5e4))
+    );
+    // a scaling that close to one leaves too many purchases to add up
+    let barely_scaled = CostModel::Superexponential {
+        base: d(3.0),
+        ratio: d(1.0),
+        scaling: d(1.0 + 1e-12),
+    };
+    assert!(barely_scaled.total_cost(&d(0.0), &d(1e5)).is_nan());
+    assert!(barely_scaled
+        .total_cost(&d(0.0), &d(1e4))
+        .eq_tolerance(&brute_force_total(&barely_scaled, 0.0, 10_000), &tolerance));
+}
+
+#[test]
+fn affordable_counts_match_brute_force() {
+    for model in models() {
+        for owned in [0.0, 5.0, 17.0] {
+            for count in 0..30 {
+                // right at a total, the summation order decides
+                let total = brute_force_total(&model, owned, count);
+                for budget in [total * d(1.000_000_001), total * d(0.999_999_999)] {
+                    assert_eq!(
+                        model.max_affordable(&d(owned), &budget),
+                        brute_force_affordable(&model, owned, &budget),
+                        "{:?} owned {} budget {}",
+                        model,
+                        owned,
+                        budget
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn huge_budgets() {
+    // n * (n + 1) / 2 <= 1e100
+    let linear = CostModel::Linear {
+        base: d(1.0),
+        increment: d(1.0),
+    };
+    let count = linear.max_affordable(&d(0.0), &d(1e100));
+    assert!(count.eq_tolerance(&d(2e100_f64.sqrt()), &d(1e-12)));
+    assert!(count.is_integer());
+
+    // (10^n - 1) / 9 <= 1e1000
+    let exponential = CostModel::Exponential {
+        base: d(1.0),
+        ratio: d(10.0),
+    };
+    assert_eq!(
+        exponential.max_affordable(&d(0.0), &from_mantissa_exponent(1.0, 1000.0)),
+        d(1000.0)
+    );
+    assert_eq!(
+        exponential.max_affordable(&d(500.0), &from_mantissa_exponent(1.0, 1000.0)),
+        d(500.0)
+    );
+
+    // 10^(n * (n - 1) / 2) <= 1e1000 up to n = 45
+    let superexponential = CostModel::Superexponential {
+        base: d(1.0),
+        ratio: d(1.0),
+        scaling: d(10.0),
+    };
+    assert_eq!(
+        superexponential.max_affordable(&d(0.0), &from_mantissa_exponent(1.0, 1000.0)),
+        d(46.0)
+    );
+
+    // sum of k^2 up to n is about n^3 / 3
+    let squares = CostModel::Polynomial {
+        base: d(1.0),
+        power: 2.0,
+    };
+    let count = squares.max_affordable(&d(0.0), &d(1e60));
+    assert!(count.eq_tolerance(&d(3e60_f64.cbrt()), &d(1e-12)));
+    assert!(count.is_integer());
+
+    assert_eq!(linear.max_affordable(&d(0.0), &d(0.5)), d(0.0));
+    assert_eq!(linear.max_affordable(&d(0.0), &Decimal::nan()), d(0.0));
+}
+
+#[test]
+fn free_purchases() {
+    // without a limit, the count is the true infinity rather than MAX
+    let free = [
+        CostModel::Linear {
+            base: d(0.0),
+            increment: d(0.0),
+        },
+        CostModel::Exponential {
+            base: d(0.0),
+            ratio: d(2.0),
+        },
+        CostModel::Exponential {
+            base: d(0.0),
+            ratio: d(1.0),
+        },
+        CostModel::Polynomial {
+            base: d(0.0),
+            power: 2.0,
+        },
+        CostModel::Superexponential {
+            base: d(0.0),
+            ratio: d(2.0),
+            scaling: d(1.1),
+        },
+        CostModel::Piecewise(vec![
+            (
+                d(0.0),
+                CostModel::Linear {
+                    base: d(10.0),
+                    increment: d(0.0),
+                },
+            ),
+            (
+                d(3.0),
+                CostModel::Linear {
+                    base: d(0.0),
+                    increment: d(0.0),
+                },
+            ),
+        ]),
+    ];
+    for model in free {
+        assert_eq!(
+            model.max_affordable(&d(0.0), &d(100.0)),
+            consts::INFINITY,
+            "{model:?}"
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use break_infinity_extended::*;
+
+fn layered(string: &str) -> LayeredDecimal {
+    string.parse().unwrap()
+}
+
+#[test]
+fn conversions() {
+    for value in [
+        Decimal::new(0.0),
+        Decimal::new(-42.5),
+        Decimal::new(1e300),
+        from_mantissa_exponent(1.2345, 1e300),
+        from_mantissa_exponent(-9.87, -1e300),
+    ] {
+        let converted = LayeredDecimal::from(value);
+        assert_eq!(converted.layer(), 0);
+        assert_eq!(Decimal::try_from(converted), Ok(value));
+    }
+    assert!(LayeredDecimal::from(consts::NAN).is_nan());
+    assert_eq!(
+        LayeredDecimal::from(consts::INFINITY),
+        LayeredDecimal::INFINITY
+    );
+    assert_eq!(
+        LayeredDecimal::from(consts::NEG_INFINITY),
+        LayeredDecimal::NEG_INFINITY
+    );
+    // MAX and MIN are infinite for a Decimal, but not for a LayeredDecimal
+    let max = LayeredDecimal::from(consts::MAX);
+    assert!(max.is_finite());
+    assert_eq!((max.sign(), max.layer()), (1, 1));
+    assert_eq!(max.magnitude(), Decimal::new(consts::EXP_LIMIT));
+    assert!(max < LayeredDecimal::INFINITY);
+    assert_eq!(LayeredDecimal::from(consts::MIN), -max);
+    assert_eq!(Decimal::try_from(max), Ok(consts::MAX));
+    assert_eq!(Decimal::try_from(-max), Ok(consts::MIN));
+    assert_eq!(Decimal::try_from(max * max), Err(TryFromLayeredError));
+    assert_eq!(LayeredDecimal::new(f64::INFINITY), LayeredDecimal::INFINITY);
+    assert_eq!(
+        LayeredDecimal::new(f64::NEG_INFINITY),
+        LayeredDecimal::NEG_INFINITY
+    );
+    assert_eq!(
+        Decimal::try_from(layered("ee10")),
+        Ok(Decimal::from("1e1e10"))
+    );
+    assert_eq!(
+        Decimal::try_from(layered("e1e400")),
+        Err(TryFromLayeredError)
+    );
+    assert_eq!(
+        Decimal::try_from(layered("e-1e400")),
+        Err(TryFromLayeredError)
+    );
+
+    let tower = LayeredDecimal::from_components(-1, 7, Decimal::new(1e10));
+    assert_eq!(tower.sign(), -1);
+    assert_eq!(tower.layer(), 6);
+    assert_eq!(tower.magnitude(), Decimal::from("1e1e10"));
+    assert_eq!(tower.to_number(), f64::NEG_INFINITY);
+}
+
+#[test]
+fn parsing_and_display() {
+    assert_eq!(layered("123.5"), LayeredDecimal::new(123.5));
+    assert_eq!(layered("e5"), LayeredDecimal::new(1e5));
+    assert_eq!(layered("-e-5"), LayeredDecimal::new(-1e-5));
+    assert_eq!(layered("10^^3"), layered("1e1e10"));
+    assert_eq!(layered("10^^5"), layered("ee1e1e10"));
+    assert_eq!(layered("(e^7)1e1e10"), layered("10^^10"));
+    assert_eq!(layered("2^^4"), LayeredDecimal::new(65536.0));
+
+    for value in [
+        layered("-123.5"),
+        layered("e1e400"),
+        layered("e-1e400"),
+        layered("10^^5"),
+        layered("10^^100"),
+        LayeredDecimal::INFINITY,
+    ] {
+        assert_eq!(layered(&value.to_string()), value);
+    }
+    assert_eq!(
+        layered("10^^5").to_string(),
+        "ee1.0000000000000000e+10000000000"
+    );
+    assert_eq!(format!("{:.2}", layered("10^^9")), "(e^6)1.00e+10000000000");
+    assert_eq!(LayeredDecimal::NAN.to_string(), "NaN");
+
+    assert_eq!("".parse::<LayeredDecimal>(), Err(ParseDecimalError::Empty));
+    assert_eq!(
+        "ee".parse::<LayeredDecimal>(),
+        Err(ParseDecimalError::InvalidMantissa { offset: 2 })
+    );
+    assert_eq!(
+        " -eex".parse::<LayeredDecimal>(),
+        Err(ParseDecimalError::InvalidMantissa { offset: 4 })
+    );
+    assert_eq!(
+        "(e^x)5".parse::<LayeredDecimal>(),
+        Err(ParseDecimalError::InvalidExponent { offset: 3 })
+    );
+    assert_eq!(
+        "10^^5x".parse::<LayeredDecimal>(),
+        Err(ParseDecimalError::TrailingCharacters { offset: 5 })
+    );
+}
+
+#[test]
+fn arithmetic() {
+    let small = LayeredDecimal::new(1e100);
+    assert_eq!(small + small, LayeredDecimal::new(2e100));
+    assert_eq!(small * small, LayeredDecimal::new(1e200));
+    assert_eq!(small / small, LayeredDecimal::ONE);
+    assert_eq!(small - small, LayeredDecimal::ZERO);
+
+    // the Decimal range is left and entered again
+    let big = layered("1e1e308") * layered("1e1e308");
+    assert_eq!(big, layered("e2e308"));
+    let quotient = big / layered("1e1e308");
+    assert_eq!(quotient.layer(), 0);
+    assert!((quotient.log10().to_number() / 1e308 - 1.0).abs() < 1e-12);
+    assert_eq!(layered("e1e400") + layered("e1e400"), layered("e1e400"));
+    assert_eq!(layered("e1e400") - layered("e1e400"), LayeredDecimal::ZERO);
+    assert_eq!(layered("e1e400") + small, layered("e1e400"));
+    assert_eq!(layered("e1e400") / layered("e1e399"), layered("e9e399"));
+    assert_eq!(layered("e1e400").recip(), layered("e-1e400"));
+    assert_eq!(layered("e-1e400") * layered("e1e400"), LayeredDecimal::ONE);
+    assert_eq!(layered("ee1e400") * layered("ee1e400"), layered("ee1e400"));
+
+    assert_eq!(-layered("e1e400"), layered("-e1e400"));
+    assert_eq!(layered("-e1e400").abs(), layered("e1e400"));
+    assert!((LayeredDecimal::INFINITY - LayeredDecimal::INFINITY).is_nan());
+    assert!((LayeredDecimal::ZERO * LayeredDecimal::INFINITY).is_nan());
+
+    assert_eq!(
+        LayeredDecimal::new(2.0).powf(10.0),
+        LayeredDecimal::new(1024.0)
+    );
+    assert_eq!(
+        LayeredDecimal::new(-2.0).powf(3.0),
+        LayeredDecimal::new(-8.0)
+    );
+    assert!(LayeredDecimal::new(-2.0).powf(0.5).is_nan());
+    assert_eq!(
+        LayeredDecimal::new(10.0).pow(&layered("e1e400")),
+        layered("ee1e400")
+    );
+    assert_eq!(layered("e1e400").log10(), layered("1e400"));
+    assert_eq!(layered("ee1e400").log10(), layered("e1e400"));
+    assert_eq!(layered("e-1e400").log10(), layered("-1e400"));
+    assert_eq!(layered("1e400").pow10(), layered("e1e400"));
+    assert_eq!(layered("e1e400").sqrt(), layered("e5e399"));
+    assert!(LayeredDecimal::new(1e300)
+        .ln()
+        .to_number()
+        .eq(&(300.0 * std::f64::consts::LN_10)));
+}
+
+#[test]
+fn ordering() {
+    let values = [
+        LayeredDecimal::NEG_INFINITY,
+        layered("-ee1e400"),
+        layered("-e1e400"),
+        LayeredDecimal::new(-1.0),
+        layered("-e-1e400"),
+        layered("-ee-1e400"),
+        LayeredDecimal::ZERO,
+        layered("ee-1e400"),
+        layered("e-1e400"),
+        LayeredDecimal::new(1e-300),
+        LayeredDecimal::ONE,
+        LayeredDecimal::new(1e300),
+        layered("e1e400"),
+        layered("e2e400"),
+        layered("ee1e400"),
+        LayeredDecimal::INFINITY,
+    ];
+    for pair in values.windows(2) {
+        assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+    }
+    assert_eq!(LayeredDecimal::NAN.partial_cmp(&LayeredDecimal::ONE), None);
+}
+
+#[test]
+fn hyperoperators() {
+    let two = LayeredDecimal::new(2.0);
+    let ten = LayeredDecimal::new(10.0);
+
+    assert_eq!(two.tetrate(0.0), LayeredDecimal::ONE);
+    assert_eq!(two.tetrate(3.0), LayeredDecimal::new(16.0));
+    assert_eq!(two.tetrate(-0.5), LayeredDecimal::new(0.5));
+    assert!(two.tetrate(-1.5).is_nan());
+    assert_eq!(ten.tetrate(5.0), layered("ee1e1e10"));
+    assert_eq!(ten.tetrate(1e6).layer(), 999_997);
+    assert_eq!(ten.tetrate(1e20), LayeredDecimal::INFINITY);
+    // converges to the fixed point of 1.2^x
+    let converged = LayeredDecimal::new(1.2).tetrate(1e10).to_number();
+    assert!((1.2_f64.powf(converged) - converged).abs() < 1e-12);
+
+    assert_eq!(ten.tetrate(5.0).slog(10.0), 5.0);
+    assert_eq!(layered("10^^1000").slog(10.0), 1000.0);
+    assert_eq!(layered("2^^1000").slog(2.0), 1000.0);
+    assert!((two.tetrate(3.5).slog(2.0) - 3.5).abs() < 1e-9);
+    assert!(LayeredDecimal::new(-5.0).slog(10.0).is_nan());
+
+    assert_eq!(
+        layered("10^^1000").iterated_log(10.0, 995),
+        layered("10^^5")
+    );
+    assert_eq!(layered("10^^5").layer_add(995.0, 10.0), layered("10^^1000"));
+    assert_eq!(
+        layered("10^^1000").layer_add(-995.0, 10.0),
+        layered("10^^5")
+    );
+    assert!((layered("10^^3").layer_add(0.5, 10.0).slog(10.0) - 3.5).abs() < 1e-9);
+
+    assert!((LayeredDecimal::new(27.0).ssqrt().to_number() - 3.0).abs() < 1e-12);
+    let root = LayeredDecimal::new(0.8).ssqrt().to_number();
+    assert!((root.powf(root) - 0.8).abs() < 1e-12);
+    let root = layered("e1e400").ssqrt();
+    let check = root.pow(&root).log10().log10().to_number();
+    assert!((check - 400.0).abs() < 1e-9);
+    let root = layered("ee1e400").ssqrt();
+    assert_eq!(root.pow(&root), layered("ee1e400"));
+    assert!(LayeredDecimal::new(0.5).ssqrt().is_nan());
+
+    assert_eq!(two.pentate(3.0), LayeredDecimal::new(65536.0));
+    assert_eq!(two.pentate(4.0), two.tetrate(65536.0));
+    assert_eq!(ten.pentate(2.0), ten.tetrate(10.0));
+    assert_eq!(ten.pentate(3.0), LayeredDecimal::INFINITY);
+}
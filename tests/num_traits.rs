@@ -0,0 +1,97 @@
+use break_infinity_extended::*;
+use num_traits::{Bounded, FromPrimitive, Inv, MulAdd, Num, One, Pow, Signed, ToPrimitive, Zero};
+
+fn sum_of_squares<T: Num + Copy>(values: &[T]) -> T {
+    values.iter().fold(T::zero(), |acc, &v| acc + v * v)
+}
+
+#[test]
+fn generic() {
+    assert_eq!(sum_of_squares(&[1.0, 2.0, 3.0]), 14.0);
+    assert_eq!(
+        sum_of_squares(&[Decimal::new(1.0), Decimal::new(2.0), Decimal::new(3.0)]),
+        Decimal::new(14.0)
+    );
+}
+
+#[test]
+fn traits() {
+    assert_eq!(Decimal::zero(), consts::ZERO);
+    assert!(Decimal::new(-0.0).is_zero());
+    for value in [
+        consts::ZERO,
+        Decimal::new(-0.0),
+        consts::ALMOST_ZERO,
+        from_mantissa_exponent_no_normalize(1.0, consts::NEG_EXP_LIMIT),
+        consts::ONE,
+        consts::NAN,
+        consts::MAX,
+    ] {
+        assert_eq!(value.is_zero(), Zero::is_zero(&value), "{value:?}");
+    }
+    assert!(Decimal::one().is_one());
+    // the bounds are the largest finite values, MAX and MIN count as infinite
+    let (max, min) = (
+        <Decimal as Bounded>::max_value(),
+        <Decimal as Bounded>::min_value(),
+    );
+    assert_eq!(max, consts::MAX_FINITE);
+    assert_eq!(min, consts::MIN_FINITE);
+    // the inherent methods keep returning MAX and MIN
+    assert_eq!(Decimal::max_value(), consts::MAX);
+    assert_eq!(Decimal::min_value(), consts::MIN);
+    for bound in [max, min] {
+        assert!(bound.is_finite() && bound.is_normalized());
+    }
+    assert!(max < consts::MAX && max > Decimal::new(f64::MAX));
+    assert!(min > consts::MIN && min < Decimal::new(f64::MIN));
+    assert!(consts::MAX.is_infinite() && consts::MIN.is_infinite());
+
+    assert_eq!(
+        Decimal::from_str_radix("1.5e1000", 10),
+        Ok(from_mantissa_exponent(1.5, 1000.0))
+    );
+    assert_eq!(
+        Decimal::from_str_radix("ff", 16),
+        Err(ParseDecimalError::UnsupportedRadix { radix: 16 })
+    );
+
+    assert_eq!(Signed::abs(&Decimal::new(-3.0)), Decimal::new(3.0));
+    assert_eq!(
+        Decimal::new(5.0).abs_sub(&Decimal::new(3.0)),
+        Decimal::new(2.0)
+    );
+    assert_eq!(Decimal::new(3.0).abs_sub(&Decimal::new(5.0)), consts::ZERO);
+    assert_eq!(Decimal::new(-1e100).signum(), consts::NEG_ONE);
+    assert_eq!(Decimal::new(1e100).signum(), consts::ONE);
+
+    assert_eq!(Decimal::from_i64(-42), Some(Decimal::new(-42.0)));
+    assert_eq!(Decimal::from_u64(42), Some(Decimal::new(42.0)));
+    assert_eq!(Decimal::from_f64(1.5e300), Some(Decimal::new(1.5e300)));
+
+    assert_eq!(Decimal::new(42.7).to_i64(), Some(42));
+    assert_eq!(Decimal::new(-42.7).to_i64(), Some(-42));
+    assert_eq!(Decimal::new(-1.0).to_u64(), None);
+    assert_eq!(Decimal::new(1e20).to_i64(), None);
+    assert_eq!(
+        Decimal::new(1e20).to_u128(),
+        Some(100_000_000_000_000_000_000)
+    );
+    assert_eq!(consts::NAN.to_i32(), None);
+    assert_eq!(
+        ToPrimitive::to_f64(&from_mantissa_exponent(1.0, 1000.0)),
+        Some(f64::INFINITY)
+    );
+
+    assert_eq!(
+        Pow::pow(Decimal::new(2.0), Decimal::new(10.0)).to_number(),
+        1024.0
+    );
+    assert_eq!(Pow::pow(Decimal::new(2.0), 10_i32).to_number(), 1024.0);
+    assert_eq!(Pow::pow(Decimal::new(4.0), 0.5_f64).to_number(), 2.0);
+    assert_eq!(Decimal::new(4.0).inv(), Decimal::new(0.25));
+    assert_eq!(
+        MulAdd::mul_add(Decimal::new(2.0), Decimal::new(3.0), Decimal::new(4.0)),
+        Decimal::new(10.0)
+    );
+}
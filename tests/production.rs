@@ -0,0 +1,170 @@
+use break_infinity_extended::{production::*, *};
+use std::cell::Cell;
+
+fn d(value: f64) -> Decimal {
+    Decimal::new(value)
+}
+
+#[test]
+fn closed_form() {
+    // a_0(t) = a_0 + a_1 r_0 t + a_2 r_0 r_1 t^2 / 2 + a_3 r_0 r_1 r_2 t^3 / 6
+    let amounts = [d(5.0), d(4.0), d(3.0), d(2.0)];
+    let rates = [d(1.5), d(2.0), d(0.5)];
+    let t = 3.0;
+    let progress = Integrator::default().advance(&amounts, &rates, &d(t));
+    let expected = [
+        5.0 + 4.0 * 1.5 * t
+            + 3.0 * 1.5 * 2.0 * t * t / 2.0
+            + 2.0 * 1.5 * 2.0 * 0.5 * t * t * t / 6.0,
+        4.0 + 3.0 * 2.0 * t + 2.0 * 2.0 * 0.5 * t * t / 2.0,
+        3.0 + 2.0 * 0.5 * t,
+        2.0,
+    ];
+    for (amount, expected) in progress.amounts.iter().zip(expected) {
+        assert!(amount.eq_tolerance(&d(expected), &d(1e-14)));
+    }
+    assert!(progress.checkpoints.is_empty());
+
+    // far beyond f64: 8 tiers producing 1e100 each per second, for a million seconds
+    let mut amounts = vec![d(0.0); 8];
+    amounts[7] = d(1.0);
+    let rates = vec![d(1e100); 7];
+    let progress = Integrator::default().advance(&amounts, &rates, &d(1e6));
+    // (1e106)^7 / 7!
+    assert!(progress.amounts[0]
+        .eq_tolerance(&(from_mantissa_exponent(1.0, 742.0) / d(5040.0)), &d(1e-13)));
+    assert_eq!(progress.amounts[7], d(1.0));
+
+    let unchanged = Integrator::default().advance(&amounts, &rates, &d(0.0));
+    assert_eq!(unchanged.amounts, amounts);
+}
+
+#[test]
+fn checkpoints() {
+    let amounts = [d(0.0), d(0.0), d(1.0)];
+    let rates = [d(2.0), d(3.0)];
+    let integrator = Integrator {
+        checkpoints: 4,
+        ..Integrator::default()
+    };
+
+    let progress = integrator.advance(&amounts, &rates, &d(8.0));
+    let times: Vec<Decimal> = progress.checkpoints.iter().map(|c| c.time).collect();
+    assert_eq!(times, vec![d(2.0), d(4.0), d(6.0), d(8.0)]);
+    for checkpoint in &progress.checkpoints {
+        let t = checkpoint.time.to_number();
+        // 1 * 3 * 2 * t^2 / 2
+        assert!(checkpoint.amounts[0].eq_tolerance(&d(3.0 * t * t), &d(1e-14)));
+        assert!(checkpoint.amounts[1].eq_tolerance(&d(3.0 * t), &d(1e-14)));
+    }
+    assert_eq!(progress.checkpoints[3].amounts, progress.amounts);
+
+    // constant rates don't need any refinement
+    let adaptive = integrator.advance_with(&amounts, |_| rates.to_vec(), &d(8.0));
+    assert_eq!(adaptive.checkpoints.len(), 4);
+    for (adaptive, exact) in adaptive.checkpoints.iter().zip(&progress.checkpoints) {
+        assert_eq!(adaptive.time, exact.time);
+        for (a, b) in adaptive.amounts.iter().zip(&exact.amounts) {
+            assert!(a.eq_tolerance(b, &d(1e-12)));
+        }
+    }
+}
+
+#[test]
+fn non_linear_rates() {
+    // the currency multiplies its own production: a_0' = a_0 * a_1 with a_1 = 1, so a_0 = e^t
+    let amounts = [d(1.0), d(1.0)];
+    let progress =
+        Integrator::default().advance_with(&amounts, |amounts| vec![amounts[0]], &d(5.0));
+    assert!(progress.amounts[0].eq_tolerance(&d(5.0_f64.exp()), &d(1e-6)));
+
+    // a_1' = 1, a_0' = a_1 * a_0^0.5 gives a_0 = (1 + t^2 / 4)^2 from a_0 = 1, a_1 = 0
+    let amounts = [d(1.0), d(0.0), d(1.0)];
+    let integrator = Integrator {
+        checkpoints: 3,
+        tolerance: 1e-10,
+        ..Integrator::default()
+    };
+    let progress =
+        integrator.advance_with(&amounts, |amounts| vec![amounts[0].sqrt(), d(1.0)], &d(6.0));
+    for checkpoint in &progress.checkpoints {
+        let t = checkpoint.time.to_number();
+        let expected = (1.0 + t * t / 4.0).powi(2);
+        assert!(checkpoint.amounts[0].eq_tolerance(&d(expected), &d(1e-6)));
+        assert!(checkpoint.amounts[1].eq_tolerance(&checkpoint.time, &d(1e-12)));
+    }
+
+    // a handful of steps can't reach the tolerance, but still cover the whole time
+    let rushed = Integrator {
+        max_steps: 5,
+        ..Integrator::default()
+    };
+    let progress = rushed.advance_with(&[d(1.0), d(1.0)], |amounts| vec![amounts[0]], &d(5.0));
+    assert!(progress.amounts[0].is_finite());
+    assert!(progress.amounts[0] > d(1.0));
+
+    // however far off the tolerance is, the steps stay bounded
+    let calls = Cell::new(0);
+    let strict = Integrator {
+        tolerance: 0.0,
+        max_steps: 50,
+        ..Integrator::default()
+    };
+    let progress = strict.advance_with(
+        &[d(1.0), d(1.0)],
+        |amounts| {
+            calls.set(calls.get() + 1);
+            vec![amounts[0]]
+        },
+        &d(5.0),
+    );
+    assert!(progress.amounts[0].is_finite());
+    assert!(progress.amounts[0] > d(1.0));
+    assert!(calls.get() <= 3 * 51);
+}
+
+#[test]
+fn nan_rates() {
+    // NaN rates can't be refined, so the rest of the time is taken at once
+    let nan_calls = Cell::new(0);
+    let integrator = Integrator {
+        checkpoints: 2,
+        ..Integrator::default()
+    };
+    let progress = integrator.advance_with(
+        &[d(1.0), d(1.0)],
+        |amounts| {
+            if amounts[0] > d(100.0) {
+                nan_calls.set(nan_calls.get() + 1);
+                vec![Decimal::nan()]
+            } else {
+                vec![amounts[0]]
+            }
+        },
+        &d(5.0),
+    );
+    assert!(progress.amounts[0].is_nan());
+    assert!(progress.checkpoints[0].amounts[0].is_finite());
+    assert!(progress.checkpoints[1].amounts[0].is_nan());
+    assert!(nan_calls.get() <= 4);
+
+    // a full step and two half steps, then the rest at once
+    let calls = Cell::new(0);
+    let progress = Integrator::default().advance_with(
+        &[d(1.0), d(1.0), d(1.0)],
+        |_| {
+            calls.set(calls.get() + 1);
+            vec![Decimal::nan(), d(1.0)]
+        },
+        &d(1e10),
+    );
+    assert_eq!(calls.get(), 4);
+    assert!(progress.amounts[0].is_nan());
+    assert_eq!(progress.amounts[2], d(1.0));
+}
+
+#[test]
+#[should_panic(expected = "a chain of 3 amounts needs 2 rates")]
+fn rates_have_to_match_the_chain() {
+    Integrator::default().advance(&[d(1.0), d(2.0), d(3.0)], &[d(1.0)], &d(1.0));
+}
@@ -0,0 +1,225 @@
+use break_infinity_extended::{consts::*, representation::*, *};
+use serde::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens, assert_tokens, Configure, Token};
+
+const VALUES: [(f64, f64); 7] = [
+    (1.5, 300.0),
+    (-2.25, 12.0),
+    (7.123456789012345, -4000.0),
+    (1.0, 0.0),
+    (9.999999999999998, 1e15),
+    (0.0, 0.0),
+    (1.0, EXP_LIMIT),
+];
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Save {
+    #[serde(with = "as_struct")]
+    as_struct: Decimal,
+    #[serde(with = "as_string")]
+    as_string: Decimal,
+    #[serde(with = "as_tuple")]
+    as_tuple: Decimal,
+    #[serde(with = "as_f64_lossy")]
+    as_f64: Decimal,
+}
+
+fn save(value: Decimal) -> Save {
+    Save {
+        as_struct: value,
+        as_string: value,
+        as_tuple: value,
+        as_f64: value,
+    }
+}
+
+#[test]
+fn representations() {
+    let json = serde_json::to_string(&save(from_mantissa_exponent(-2.5, -7.0))).unwrap();
+    assert_eq!(
+        json,
+        r#"{"as_struct":{"mantissa":-2.5,"exponent":-7.0},"as_string":"-2.5e-7","as_tuple":[-2.5,-7.0],"as_f64":-2.5e-7}"#
+    );
+
+    let json = serde_json::to_string(&save(NAN)).unwrap();
+    assert_eq!(
+        json,
+        r#"{"as_struct":{"mantissa":null,"exponent":null},"as_string":"NaN","as_tuple":[null,null],"as_f64":null}"#
+    );
+
+    let huge = serde_json::to_string(&save(from_mantissa_exponent(1.5, 1000.0))).unwrap();
+    assert!(huge.contains(r#""as_string":"1.5e1000""#));
+    assert!(huge.contains(r#""as_f64":null"#));
+
+    #[cfg(feature = "serde-tuple")]
+    let expected = "[1.5,300.0]";
+    #[cfg(all(feature = "serde-string", not(feature = "serde-tuple")))]
+    let expected = r#""1.5e300""#;
+    #[cfg(not(any(feature = "serde-string", feature = "serde-tuple")))]
+    let expected = r#"{"mantissa":1.5,"exponent":300.0}"#;
+    let value = from_mantissa_exponent(1.5, 300.0);
+    assert_eq!(serde_json::to_string(&value).unwrap(), expected);
+    assert_eq!(serde_json::from_str::<Decimal>(expected).unwrap(), value);
+}
+
+#[test]
+fn round_trips() {
+    for (mantissa, exponent) in VALUES {
+        let value = from_mantissa_exponent_no_normalize(mantissa, exponent);
+        let json = serde_json::to_string(&save(value)).unwrap();
+        let loaded: Save = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.as_struct.to_bits(), value.to_bits(), "{json}");
+        assert_eq!(loaded.as_string.to_bits(), value.to_bits(), "{json}");
+        assert_eq!(loaded.as_tuple.to_bits(), value.to_bits(), "{json}");
+        if value.to_number().is_finite() {
+            assert_eq!(loaded.as_f64, Decimal::new(value.to_number()), "{json}");
+        } else {
+            // JSON writes infinite numbers as null
+            assert!(loaded.as_f64.is_nan(), "{json}");
+        }
+
+        let total = TotalDecimal(value);
+        let json = serde_json::to_string(&total).unwrap();
+        assert_eq!(serde_json::from_str::<TotalDecimal>(&json).unwrap(), total);
+    }
+
+    let layered = LayeredDecimal::from(from_mantissa_exponent(3.0, 5.0)).pow10();
+    let json = serde_json::to_string(&layered).unwrap();
+    assert_eq!(
+        serde_json::from_str::<LayeredDecimal>(&json).unwrap(),
+        layered
+    );
+}
+
+#[test]
+fn lenient_deserialization() {
+    let value = from_mantissa_exponent(1.5, 300.0);
+    let forms = [
+        r#"{"mantissa":1.5,"exponent":300}"#,
+        r#"{"exponent":300.0,"mantissa":1.5,"sign":1}"#,
+        r#""1.5e300""#,
+        // break_infinity.js writes the sign of the exponent
+        r#""1.5e+300""#,
+        r#"" 15e299 ""#,
+        "[1.5,300]",
+    ];
+    for form in forms {
+        let json = format!(
+            r#"{{"as_struct":{form},"as_string":{form},"as_tuple":{form},"as_f64":{form}}}"#
+        );
+        let loaded: Save = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, save(value), "{form}");
+        assert_eq!(serde_json::from_str::<Decimal>(form).unwrap(), value);
+    }
+
+    let number: Save = serde_json::from_str(
+        r#"{"as_struct":1.5e300,"as_string":1.5e300,"as_tuple":1.5e300,"as_f64":1.5e300}"#,
+    )
+    .unwrap();
+    for loaded in [
+        number.as_struct,
+        number.as_string,
+        number.as_tuple,
+        number.as_f64,
+    ] {
+        assert!(loaded.eq_tolerance(&value, &Decimal::new(1e-15)));
+    }
+    assert_eq!(
+        serde_json::from_str::<Decimal>("42").unwrap(),
+        Decimal::new(42.0)
+    );
+    assert_eq!(
+        serde_json::from_str::<Decimal>("-42").unwrap(),
+        Decimal::new(-42.0)
+    );
+    assert_eq!(
+        serde_json::from_str::<Decimal>(r#""Infinity""#).unwrap(),
+        MAX
+    );
+    assert!(serde_json::from_str::<Decimal>("null").unwrap().is_nan());
+
+    // parts which aren't canonical are normalized, fractional exponents included
+    for form in [
+        "[50.0,0.5]",
+        r#"{"mantissa":0.05,"exponent":3.5}"#,
+        r#"{"mantissa":1581.1388300841897,"exponent":-1}"#,
+    ] {
+        let loaded = serde_json::from_str::<Decimal>(form).unwrap();
+        assert!(loaded.is_normalized(), "{form}");
+        assert!(
+            loaded.eq_tolerance(&Decimal::new(50.0 * 10.0_f64.sqrt()), &Decimal::new(1e-14)),
+            "{form}"
+        );
+    }
+
+    let error = |json| {
+        serde_json::from_str::<Decimal>(json)
+            .unwrap_err()
+            .to_string()
+    };
+    assert!(error(r#""1.5e""#).starts_with("invalid exponent at byte 4"));
+    assert!(error("[1.5]").starts_with("invalid length 1"));
+    assert!(error(r#"{"mantissa":1.5}"#).starts_with("missing field `exponent`"));
+    assert!(error(r#"{"mantissa":1,"mantissa":2}"#).starts_with("duplicate field `mantissa`"));
+    assert!(error("true").starts_with("invalid type: boolean `true`"));
+    assert!(error("[1.0,1.795e308]").starts_with(
+        "invalid value: floating point `1.795e+308`, expected an exponent within EXP_LIMIT"
+    ));
+}
+
+#[test]
+fn compact_formats() {
+    // formats which aren't human-readable are read in the form they were written in
+    let value = from_mantissa_exponent(1.5, 300.0);
+    assert_tokens(
+        &Wrapper(value).compact(),
+        &[
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Str("1.5e300"),
+        ],
+    );
+    assert_tokens(&value.compact(), &default_tokens(1.5, 300.0));
+    assert_de_tokens(
+        &value.readable(),
+        &[
+            Token::Tuple { len: 2 },
+            Token::F64(1.5),
+            Token::F64(300.0),
+            Token::TupleEnd,
+        ],
+    );
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Wrapper(#[serde(with = "as_string")] Decimal);
+
+#[cfg(all(feature = "serde-string", not(feature = "serde-tuple")))]
+fn default_tokens(mantissa: f64, exponent: f64) -> Vec<Token> {
+    let string = format!("{mantissa}e{exponent}");
+    vec![Token::Str(Box::leak(string.into_boxed_str()))]
+}
+
+#[cfg(feature = "serde-tuple")]
+fn default_tokens(mantissa: f64, exponent: f64) -> Vec<Token> {
+    vec![
+        Token::Tuple { len: 2 },
+        Token::F64(mantissa),
+        Token::F64(exponent),
+        Token::TupleEnd,
+    ]
+}
+
+#[cfg(not(any(feature = "serde-string", feature = "serde-tuple")))]
+fn default_tokens(mantissa: f64, exponent: f64) -> Vec<Token> {
+    vec![
+        Token::Struct {
+            name: "Decimal",
+            len: 2,
+        },
+        Token::Str("mantissa"),
+        Token::F64(mantissa),
+        Token::Str("exponent"),
+        Token::F64(exponent),
+        Token::StructEnd,
+    ]
+}
@@ -0,0 +1,187 @@
+use break_infinity_extended::*;
+
+const STARTS: [f64; 4] = [1.0, 2.5, 10.0, 1e3];
+const RATIOS: [f64; 6] = [1.0, 1.01, 1.07, 1.5, 2.0, 10.0];
+const ADDS: [f64; 5] = [0.0, 0.25, 1.0, 7.0, 150.0];
+const OWNED: [f64; 4] = [0.0, 1.0, 12.0, 45.0];
+
+fn d(value: f64) -> Decimal {
+    Decimal::new(value)
+}
+
+fn geometric_price(start: f64, ratio: f64, owned: f64, i: u32) -> Decimal {
+    d(start) * d(ratio).pow(&d(owned + i as f64))
+}
+
+fn arithmetic_price(start: f64, add: f64, owned: f64, i: u32) -> Decimal {
+    d(start + (owned + i as f64) * add)
+}
+
+fn brute_force_sum(price: impl Fn(u32) -> Decimal, items: u32) -> Decimal {
+    sum_compensated((0..items).map(price))
+}
+
+fn assert_close(actual: Decimal, expected: Decimal, context: &str) {
+    assert!(
+        actual.eq_tolerance(&expected, &d(1e-9)),
+        "{}: {} != {}",
+        context,
+        actual,
+        expected
+    );
+}
+
+// budgets just above and just below the price of every count of items,
+// which have to afford exactly that count and one less
+fn check_afford(
+    afford: impl Fn(&Decimal) -> Decimal,
+    price: impl Fn(u32) -> Decimal,
+    context: &str,
+) {
+    for items in 0..30 {
+        let total = brute_force_sum(&price, items);
+        let above = total + price(items) * d(1e-6);
+        assert_eq!(
+            afford(&above),
+            d(items as f64),
+            "{}, budget {} above {} items",
+            context,
+            above,
+            items
+        );
+        if items > 0 {
+            let below = total - price(items - 1) * d(1e-6);
+            assert_eq!(
+                afford(&below),
+                d(items as f64 - 1.0),
+                "{}, budget {} below {} items",
+                context,
+                below,
+                items
+            );
+        }
+    }
+}
+
+#[test]
+fn sum_geometric_series_matches_brute_force() {
+    for start in STARTS {
+        for ratio in RATIOS {
+            for owned in OWNED {
+                for items in 0..40 {
+                    let price = |i| geometric_price(start, ratio, owned, i);
+                    assert_close(
+                        sum_geometric_series(&d(items as f64), &d(start), &d(ratio), &d(owned)),
+                        brute_force_sum(price, items),
+                        &format!(
+                            "start {} ratio {} owned {} items {}",
+                            start, ratio, owned, items
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn sum_arithmetic_series_matches_brute_force() {
+    for start in STARTS {
+        for add in ADDS {
+            for owned in OWNED {
+                for items in 0..40 {
+                    let price = |i| arithmetic_price(start, add, owned, i);
+                    assert_close(
+                        sum_arithmetic_series(&d(items as f64), &d(start), &d(add), &d(owned)),
+                        brute_force_sum(price, items),
+                        &format!(
+                            "start {} add {} owned {} items {}",
+                            start, add, owned, items
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn afford_geometric_series_matches_brute_force() {
+    for start in STARTS {
+        for ratio in RATIOS {
+            for owned in OWNED {
+                check_afford(
+                    |budget| afford_geometric_series(budget, &d(start), &d(ratio), &d(owned)),
+                    |i| geometric_price(start, ratio, owned, i),
+                    &format!("start {} ratio {} owned {}", start, ratio, owned),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn afford_arithmetic_series_matches_brute_force() {
+    for start in STARTS {
+        for add in ADDS {
+            for owned in OWNED {
+                check_afford(
+                    |budget| afford_arithmetic_series(budget, &d(start), &d(add), &d(owned)),
+                    |i| arithmetic_price(start, add, owned, i),
+                    &format!("start {} add {} owned {}", start, add, owned),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn edge_cases() {
+    // a ratio of one is a constant price
+    assert_eq!(
+        sum_geometric_series(&d(7.0), &d(3.0), &d(1.0), &d(5.0)),
+        d(21.0)
+    );
+    assert_eq!(
+        afford_geometric_series(&d(20.0), &d(3.0), &d(1.0), &d(5.0)),
+        d(6.0)
+    );
+
+    // zero items cost nothing
+    assert_eq!(
+        sum_geometric_series(&d(0.0), &d(3.0), &d(2.0), &d(5.0)),
+        d(0.0)
+    );
+    assert_eq!(
+        sum_arithmetic_series(&d(0.0), &d(3.0), &d(2.0), &d(5.0)),
+        d(0.0)
+    );
+
+    // budgets below the first price afford nothing
+    assert_eq!(
+        afford_geometric_series(&d(95.0), &d(100.0), &d(1.5), &d(0.0)),
+        d(0.0)
+    );
+    assert_eq!(
+        afford_geometric_series(&d(0.0), &d(100.0), &d(1.5), &d(0.0)),
+        d(0.0)
+    );
+    assert_eq!(
+        afford_arithmetic_series(&d(95.0), &d(100.0), &d(10.0), &d(0.0)),
+        d(0.0)
+    );
+    assert_eq!(
+        afford_arithmetic_series(&d(0.0), &d(100.0), &d(0.0), &d(0.0)),
+        d(0.0)
+    );
+
+    // (n/2)*(2a+(n-1)d): 10 + 13 + 16 + 19
+    assert_eq!(
+        sum_arithmetic_series(&d(4.0), &d(10.0), &d(3.0), &d(0.0)),
+        d(58.0)
+    );
+    assert_eq!(
+        afford_arithmetic_series(&d(58.0), &d(10.0), &d(3.0), &d(0.0)),
+        d(4.0)
+    );
+}